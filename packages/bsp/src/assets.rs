@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use thiserror::Error;
+use tsify::Tsify;
+use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
+
+use crate::{
+    bsp::{BSP, BSPError, EntitiesError, PakfileError},
+    entities::KeyValue,
+    gamelump::DetailPropKind,
+    lump::LumpKind,
+    materials::normalise,
+};
+
+const SKYBOX_FACES: [&str; 6] = ["bk", "dn", "ft", "lf", "rt", "up"];
+const SOUND_CHARS: &[char] = &['*', '#', '@', '>', '<', '^', ')', '(', '}', '$', '!', '?', '&', '~', '`', '+', '%'];
+const MATERIAL_KEYS: [&str; 5] = ["texture", "material", "ropematerial", "spritename", "overlaymaterial"];
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct Assets(pub Vec<Asset>);
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct Asset {
+    pub path: String,
+    pub kind: AssetKind,
+    pub packed: bool,
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Tsify)]
+#[serde(tag = "type", content = "value")]
+pub enum AssetKind {
+    Material,
+    Model,
+    Sound,
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub enum AssetsError {
+    #[error(transparent)]
+    Lump(#[from] BSPError),
+
+    #[error(transparent)]
+    Entities(#[from] EntitiesError),
+
+    #[error(transparent)]
+    Pakfile(#[from] PakfileError),
+}
+
+impl From<AssetsError> for JsValue {
+    fn from(value: AssetsError) -> Self {
+        JsValue::from(JsError::new(&format!("{:?}", value)))
+    }
+}
+
+fn material_path(name: &str) -> String {
+    let name = normalise(name);
+    let name = name.strip_prefix("materials/").unwrap_or(&name);
+    let name = name.strip_suffix(".vmt").or_else(|| name.strip_suffix(".spr")).unwrap_or(name);
+    format!("materials/{}.vmt", name)
+}
+
+fn sound_path(name: &str) -> String {
+    let name = normalise(name.trim_start_matches(SOUND_CHARS));
+    match name.starts_with("sound/") {
+        true => name,
+        false => format!("sound/{}", name),
+    }
+}
+
+fn keyvalue_asset(key: &str, value: &str) -> Option<(AssetKind, String)> {
+    let value = value.trim();
+    let lower = value.to_lowercase();
+
+    // `*<index>` names a brush model, `*` before a sound path is a sound character.
+    let brush_model = value
+        .strip_prefix('*')
+        .is_some_and(|index| !index.is_empty() && index.bytes().all(|byte| byte.is_ascii_digit()));
+
+    match lower.rsplit_once('.').map(|(_, extension)| extension) {
+        _ if value.is_empty() || brush_model => None,
+        Some("mdl") => Some((AssetKind::Model, normalise(value))),
+        Some("vmt" | "spr") => Some((AssetKind::Material, material_path(value))),
+        Some("wav" | "mp3" | "ogg") => Some((AssetKind::Sound, sound_path(value))),
+        _ if MATERIAL_KEYS.contains(&key) => Some((AssetKind::Material, material_path(value))),
+        _ => None,
+    }
+}
+
+struct Collector {
+    assets: BTreeMap<String, (AssetKind, Vec<String>)>,
+}
+
+impl Collector {
+    fn add(&mut self, kind: AssetKind, path: String, reference: &str) {
+        let (_, references) = self.assets.entry(path).or_insert_with(|| (kind, vec![]));
+        if !references.iter().any(|existing| existing == reference) {
+            references.push(reference.to_string());
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn assets(&self) -> Result<Assets, AssetsError> {
+        let mut collector = Collector { assets: BTreeMap::new() };
+
+        for material in self.materials()?.0 {
+            if material.faces > 0 {
+                collector.add(AssetKind::Material, material.path.clone(), "brush");
+            }
+            if material.overlays > 0 {
+                collector.add(AssetKind::Material, material.path, "overlay");
+            }
+        }
+
+        for model in self.static_props()?.models {
+            collector.add(AssetKind::Model, normalise(&model), "static_prop");
+        }
+
+        let detail_props = self.detail_props()?;
+        for model in &detail_props.models {
+            collector.add(AssetKind::Model, normalise(model), "detail_prop");
+        }
+        if detail_props.props.iter().any(|prop| !matches!(prop.kind, DetailPropKind::Model)) {
+            collector.add(AssetKind::Material, material_path(&detail_props.sprite_material), "detail_sprite");
+        }
+
+        for entity in self.entities()?.iter() {
+            let classname = entity.get("classname").unwrap_or("entity");
+
+            for KeyValue { key, value, .. } in &entity.keyvalues {
+                let key = key.to_lowercase();
+                let reference = format!("{}.{}", classname, key);
+
+                if key == "skyname" && !value.is_empty() {
+                    for face in SKYBOX_FACES {
+                        collector.add(AssetKind::Material, material_path(&format!("skybox/{}{}", value, face)), &reference);
+                    }
+                } else if let Some((kind, path)) = keyvalue_asset(&key, value) {
+                    collector.add(kind, path, &reference);
+                }
+            }
+        }
+
+        let packed = match self.raw_lump(LumpKind::Pakfile as usize)?.is_empty() {
+            true => HashSet::new(),
+            false => self.pakfile()?.paths().into_iter().collect::<HashSet<String>>(),
+        };
+
+        Ok(Assets(
+            collector
+                .assets
+                .into_iter()
+                .map(|(path, (kind, references))| Asset {
+                    packed: packed.contains(&path),
+                    path,
+                    kind,
+                    references,
+                })
+                .collect(),
+        ))
+    }
+}
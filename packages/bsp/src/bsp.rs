@@ -1,32 +1,37 @@
 use std::{
-    io::{BufReader, Cursor},
+    io::{self, BufReader, Cursor, Write},
     vec,
 };
 
 use bincode::{
     Decode, Encode,
     de::Decoder,
+    enc::Encoder,
     error::{DecodeError, EncodeError},
     impl_borrow_decode,
 };
 use derive_more::Display;
+use lzma_rust2::{LzmaOptions, LzmaWriter};
 use thiserror::Error;
 use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
 
 use crate::{
     entities::{Entities, SyntaxError},
+    lump::LumpKind,
     pakfile::{PakError, Pakfile},
 };
 
 #[wasm_bindgen]
 #[derive(Debug, Decode)]
 pub struct BSP {
-    buf: Vec<u8>,
+    pub(crate) buf: Vec<u8>,
     pub header: BSPHeader,
 }
 
+pub const HEADER_SIZE: usize = 4 + 4 + 64 * 16 + 4;
+
 #[wasm_bindgen]
-#[derive(Debug, Clone, Copy, Decode)]
+#[derive(Debug, Clone, Copy)]
 pub struct BSPHeader {
     pub signature: BSPSignature,
     pub version: i32,
@@ -35,6 +40,67 @@ pub struct BSPHeader {
     pub lumps: [Lump; 64],
 
     pub map_revision: i32,
+    pub lump_order: LumpOrder,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LumpOrder {
+    OffsetFirst,
+    VersionFirst,
+}
+
+impl<Context> Decode<Context> for BSPHeader {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let signature = BSPSignature::decode(decoder)?;
+        let version = i32::decode(decoder)?;
+        let mut lumps = <[Lump; 64]>::decode(decoder)?;
+        let map_revision = i32::decode(decoder)?;
+
+        let lump_order = match version {
+            21 if lumps.iter().any(|lump| lump.len != 0 && (lump.offset as usize) < HEADER_SIZE) => LumpOrder::VersionFirst,
+            _ => LumpOrder::OffsetFirst,
+        };
+
+        if lump_order == LumpOrder::VersionFirst {
+            for lump in &mut lumps {
+                *lump = Lump {
+                    offset: lump.len,
+                    len: lump.version,
+                    version: lump.offset,
+                    four_cc: lump.four_cc,
+                };
+            }
+        }
+
+        Ok(BSPHeader {
+            signature,
+            version,
+            lumps,
+            map_revision,
+            lump_order,
+        })
+    }
+}
+
+impl_borrow_decode!(BSPHeader);
+
+impl Encode for BSPHeader {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        b"VBSP".encode(encoder)?;
+        self.version.encode(encoder)?;
+
+        for lump in &self.lumps {
+            let fields = match self.lump_order {
+                LumpOrder::OffsetFirst => [lump.offset, lump.len, lump.version],
+                LumpOrder::VersionFirst => [lump.version, lump.offset, lump.len],
+            };
+            fields.encode(encoder)?;
+            lump.four_cc.encode(encoder)?;
+        }
+
+        self.map_revision.encode(encoder)
+    }
 }
 
 #[wasm_bindgen]
@@ -65,7 +131,7 @@ pub struct Lump {
 }
 
 #[derive(Debug, Decode)]
-struct BSPLZMAHeader {
+pub(crate) struct BSPLZMAHeader {
     _signature: LZMASignature,
     pub actual_size: u32,
     pub lzma_size: u32,
@@ -74,7 +140,7 @@ struct BSPLZMAHeader {
 
 #[derive(Debug, Display)]
 #[display("LZMA")]
-struct LZMASignature;
+pub(crate) struct LZMASignature;
 
 impl<Context> Decode<Context> for LZMASignature {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
@@ -105,6 +171,27 @@ pub enum BSPError {
 
     #[error(transparent)]
     Lzma(#[from] lzma_rs::error::Error),
+
+    #[error(transparent)]
+    IO(#[from] io::Error),
+
+    #[error("{:#?}", self)]
+    UnexpectedLump { found: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedLumpRange { index: usize, offset: i32, len: i32 },
+
+    #[error("{:#?}", self)]
+    UnexpectedCompression { found: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedGameLump { id: String, version: u16, stride: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedGameLumpRange { id: String, offset: i32, len: i32 },
+
+    #[error("{:#?}", self)]
+    UnexpectedDispPower { found: i32 },
 }
 
 impl From<BSPError> for JsValue {
@@ -145,6 +232,46 @@ impl From<PakfileError> for JsValue {
     }
 }
 
+pub(crate) fn decompress(buf: &[u8]) -> Result<Vec<u8>, BSPError> {
+    let config = bincode::config::standard().with_fixed_int_encoding();
+
+    let (bsp_header, bytes_read): (BSPLZMAHeader, usize) = bincode::decode_from_slice(buf, config)?;
+
+    let header = LZMAHeader {
+        properties: bsp_header.properties,
+        actual_size: bsp_header.actual_size as u64,
+    };
+
+    let lzma_header_size = 5 + 8;
+
+    let mut lzma_data = vec![0u8; lzma_header_size + bsp_header.lzma_size as usize];
+
+    bincode::encode_into_slice(&header, &mut lzma_data, config)?;
+    lzma_data[lzma_header_size..].copy_from_slice(
+        buf.get(bytes_read..bytes_read + bsp_header.lzma_size as usize)
+            .ok_or(DecodeError::UnexpectedEnd {
+                additional: bsp_header.lzma_size as usize,
+            })?,
+    );
+
+    let mut out = vec![0u8; bsp_header.actual_size as usize];
+    lzma_rs::lzma_decompress(&mut Cursor::new(&lzma_data), &mut Cursor::new(&mut out))?;
+
+    Ok(out)
+}
+
+pub(crate) fn compress(buf: &[u8]) -> Result<([u8; 5], Vec<u8>), io::Error> {
+    let options = LzmaOptions::default();
+
+    let mut properties = [options.get_props(); 5];
+    properties[1..].copy_from_slice(&options.dict_size.to_le_bytes());
+
+    let mut writer = LzmaWriter::new(vec![], &options, false, false, Some(buf.len() as u64))?;
+    writer.write_all(buf)?;
+
+    Ok((properties, writer.finish()?))
+}
+
 #[wasm_bindgen]
 impl BSP {
     #[wasm_bindgen(constructor)]
@@ -156,53 +283,35 @@ impl BSP {
     }
 
     pub fn lump(&self, i: usize) -> Result<Vec<u8>, BSPError> {
-        let lump = &self.header.lumps[i];
-        let buf = self
-            .buf
-            .get(lump.offset as usize..(lump.offset + lump.len) as usize)
-            .ok_or(DecodeError::UnexpectedEnd { additional: lump.len as usize })?;
+        let buf = self.raw_lump(i)?;
 
-        match &buf[0..4] {
-            b"LZMA" => {
-                let config = bincode::config::standard().with_fixed_int_encoding();
-
-                let (bsp_header, bytes_read): (BSPLZMAHeader, usize) = bincode::decode_from_slice(buf, config)?;
-
-                let header = LZMAHeader {
-                    properties: bsp_header.properties,
-                    actual_size: bsp_header.actual_size as u64,
-                };
-
-                let lzma_header_size = 5 + 8;
-
-                let mut lzma_data = vec![0u8; lzma_header_size + bsp_header.lzma_size as usize];
-
-                bincode::encode_into_slice(&header, &mut lzma_data, config)?;
-                lzma_data[lzma_header_size..].copy_from_slice(&buf[bytes_read..]);
-
-                let mut out = vec![0u8; bsp_header.actual_size as usize];
-                lzma_rs::lzma_decompress(&mut Cursor::new(&lzma_data), &mut Cursor::new(&mut out))?;
-
-                Ok(out)
-            }
+        match buf.get(0..4) {
+            Some(b"LZMA") => decompress(buf),
             _ => Ok(buf.to_vec()),
         }
     }
 
     #[wasm_bindgen]
     pub fn entities(&self) -> Result<Entities, EntitiesError> {
-        let buf = self.lump(0)?;
-        let text = String::from_utf8_lossy_owned(buf);
-        let entities = Entities::new(text)?;
-
-        Ok(entities)
+        let buf = self.lump_of(LumpKind::Entities)?;
+        Ok(Entities::new(&buf)?)
     }
 
     #[wasm_bindgen]
     pub fn pakfile(&self) -> Result<Pakfile, PakfileError> {
-        let buf = self.lump(40)?;
+        let buf = self.lump_of(LumpKind::Pakfile)?;
         let pakfile = Pakfile::new(buf)?;
 
         Ok(pakfile)
     }
+
+    pub fn with_pakfile(&self, pakfile: &mut Pakfile) -> Result<Vec<u8>, PakfileError> {
+        let mut bsp = BSP {
+            buf: self.buf.clone(),
+            header: self.header,
+        };
+        bsp.set_lump(LumpKind::Pakfile, pakfile.save()?, false)?;
+
+        Ok(bsp.save()?)
+    }
 }
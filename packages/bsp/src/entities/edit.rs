@@ -0,0 +1,384 @@
+use std::borrow::Cow;
+
+use serde::Serialize;
+use thiserror::Error;
+use tsify::Tsify;
+use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
+
+use super::{
+    Entities, SyntaxError,
+    tokeniser::{Span, Token, Tokeniser},
+};
+use crate::{
+    bsp::{BSP, BSPError, EntitiesError},
+    lump::LumpKind,
+};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct EntityLump {
+    prefix: Vec<u8>,
+    entities: Vec<Entity>,
+    suffix: Vec<u8>,
+}
+
+// Keys and values keep their raw bytes, they are only decoded for display and matching.
+#[derive(Debug, Clone)]
+pub struct Entity {
+    keyvalues: Vec<(Vec<u8>, Vec<u8>)>,
+    source: Option<Source>,
+    trailing: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+struct Source {
+    bytes: Vec<u8>,
+    span: Span,
+    keyvalues: Vec<(Span, Span)>,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct KeyValues(pub Vec<KeyValue>);
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+    pub key_span: Option<Span>,
+    pub value_span: Option<Span>,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct EntityInfo {
+    pub index: usize,
+    pub span: Option<Span>,
+    pub keyvalues: Vec<KeyValue>,
+}
+
+#[derive(Debug, Error)]
+#[error("{:#?}", self)]
+pub enum EntityEditError {
+    UnexpectedIndex { found: usize },
+    UnexpectedCharacter { found: String },
+}
+
+impl From<EntityEditError> for JsValue {
+    fn from(value: EntityEditError) -> Self {
+        JsValue::from(JsError::new(&format!("{:?}", value)))
+    }
+}
+
+fn validate(str: &str) -> Result<(), EntityEditError> {
+    match str.contains(['"', '\0']) {
+        true => Err(EntityEditError::UnexpectedCharacter { found: str.to_string() }),
+        false => Ok(()),
+    }
+}
+
+impl EntityInfo {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.keyvalues
+            .iter()
+            .find(|keyvalue| keyvalue.key.eq_ignore_ascii_case(key))
+            .map(|keyvalue| keyvalue.value.as_str())
+    }
+
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.keyvalues
+            .iter()
+            .filter(move |keyvalue| keyvalue.key.eq_ignore_ascii_case(key))
+            .map(|keyvalue| keyvalue.value.as_str())
+    }
+}
+
+impl Entity {
+    pub fn new() -> Self {
+        Entity {
+            keyvalues: vec![],
+            source: None,
+            trailing: b"\n".to_vec(),
+        }
+    }
+
+    pub fn keyvalues(&self) -> impl Iterator<Item = (Cow<'_, str>, Cow<'_, str>)> {
+        self.keyvalues
+            .iter()
+            .map(|(key, value)| (String::from_utf8_lossy(key), String::from_utf8_lossy(value)))
+    }
+
+    pub fn raw_keyvalues(&self) -> &[(Vec<u8>, Vec<u8>)] {
+        &self.keyvalues
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.source.as_ref().map(|source| source.span)
+    }
+
+    pub fn keyvalue_spans(&self) -> Option<&[(Span, Span)]> {
+        self.source.as_ref().map(|source| source.keyvalues.as_slice())
+    }
+
+    fn info(&self, index: usize) -> EntityInfo {
+        let spans = self.keyvalue_spans();
+
+        EntityInfo {
+            index,
+            span: self.span(),
+            keyvalues: self
+                .keyvalues()
+                .enumerate()
+                .map(|(i, (key, value))| {
+                    let spans = spans.and_then(|spans| spans.get(i));
+                    KeyValue {
+                        key: key.into_owned(),
+                        value: value.into_owned(),
+                        key_span: spans.map(|(key, _)| *key),
+                        value_span: spans.map(|(_, value)| *value),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.keyvalues().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, value)| value)
+    }
+
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = Cow<'a, str>> {
+        self.keyvalues().filter(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, value)| value)
+    }
+
+    pub fn contains(&self, key: &str, value: &str) -> bool {
+        self.get_all(key).any(|v| v == value)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), EntityEditError> {
+        validate(key)?;
+        validate(value)?;
+        self.source = None;
+
+        match self.keyvalues.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key.as_bytes())) {
+            Some((_, existing)) => *existing = value.as_bytes().to_vec(),
+            None => self.keyvalues.push((key.as_bytes().to_vec(), value.as_bytes().to_vec())),
+        }
+
+        Ok(())
+    }
+
+    pub fn append(&mut self, key: &str, value: &str) -> Result<(), EntityEditError> {
+        validate(key)?;
+        validate(value)?;
+        self.source = None;
+        self.keyvalues.push((key.as_bytes().to_vec(), value.as_bytes().to_vec()));
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &str) -> usize {
+        let len = self.keyvalues.len();
+        self.keyvalues.retain(|(k, _)| !k.eq_ignore_ascii_case(key.as_bytes()));
+
+        if self.keyvalues.len() != len {
+            self.source = None;
+        }
+
+        len - self.keyvalues.len()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match &self.source {
+            Some(source) => out.extend(&source.bytes),
+            None => {
+                out.extend(b"{\n");
+                for (key, value) in &self.keyvalues {
+                    out.push(b'"');
+                    out.extend(key);
+                    out.extend(b"\" \"");
+                    out.extend(value);
+                    out.extend(b"\"\n");
+                }
+                out.push(b'}');
+            }
+        }
+
+        out.extend(&self.trailing);
+    }
+}
+
+impl Default for Entity {
+    fn default() -> Self {
+        Entity::new()
+    }
+}
+
+impl EntityLump {
+    pub fn new(buf: &[u8]) -> Result<EntityLump, SyntaxError> {
+        let mut tokens = Tokeniser::new(buf);
+
+        let mut prefix = vec![];
+        let mut entities = Vec::<Entity>::new();
+        let mut gap_start = 0;
+
+        let mut gap = |entities: &mut Vec<Entity>, gap: &[u8]| match entities.last_mut() {
+            Some(entity) => entity.trailing = gap.to_vec(),
+            None => prefix = gap.to_vec(),
+        };
+
+        let suffix = loop {
+            match tokens.next() {
+                Some((Token::OpeningBrace, open)) => {
+                    gap(&mut entities, &buf[gap_start..open.start.offset]);
+
+                    let mut keyvalues = vec![];
+                    let mut spans = vec![];
+                    let close = loop {
+                        match tokens.next() {
+                            Some((Token::String(key), key_span)) => match tokens.next() {
+                                Some((Token::String(value), value_span)) => {
+                                    keyvalues.push((key.to_vec(), value.to_vec()));
+                                    spans.push((key_span, value_span));
+                                }
+                                token => Err(SyntaxError::new(token, &tokens))?,
+                            },
+                            Some((Token::ClosingBrace, close)) => break close,
+                            token => Err(SyntaxError::new(token, &tokens))?,
+                        }
+                    };
+
+                    gap_start = close.end.offset;
+                    entities.push(Entity {
+                        keyvalues,
+                        source: Some(Source {
+                            bytes: buf[open.start.offset..close.end.offset].to_vec(),
+                            span: Span {
+                                start: open.start,
+                                end: close.end,
+                            },
+                            keyvalues: spans,
+                        }),
+                        trailing: vec![],
+                    });
+                }
+                Some((Token::Eof, eof)) => {
+                    gap(&mut entities, &buf[gap_start..eof.start.offset]);
+                    break buf[eof.start.offset..].to_vec();
+                }
+                None => {
+                    gap(&mut entities, &buf[gap_start..]);
+                    break vec![];
+                }
+                token => Err(SyntaxError::new(token, &tokens))?,
+            }
+        };
+
+        Ok(EntityLump { prefix, entities, suffix })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter()
+    }
+
+    pub fn entity(&self, index: usize) -> Option<&Entity> {
+        self.entities.get(index)
+    }
+
+    pub fn entity_mut(&mut self, index: usize) -> Result<&mut Entity, EntityEditError> {
+        self.entities.get_mut(index).ok_or(EntityEditError::UnexpectedIndex { found: index })
+    }
+
+    pub fn push(&mut self, entity: Entity) -> usize {
+        self.entities.push(entity);
+        self.entities.len() - 1
+    }
+}
+
+#[wasm_bindgen]
+impl EntityLump {
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn keyvalues(&self, index: usize) -> Option<KeyValues> {
+        self.entities.get(index).map(|entity| KeyValues(entity.info(index).keyvalues))
+    }
+
+    pub fn span(&self, index: usize) -> Option<Span> {
+        self.entities.get(index)?.span()
+    }
+
+    pub fn outline(&self) -> Entities {
+        Entities(self.entities.iter().enumerate().map(|(index, entity)| entity.info(index)).collect())
+    }
+
+    pub fn get(&self, index: usize, key: &str) -> Option<String> {
+        self.entities.get(index)?.get(key).map(Cow::into_owned)
+    }
+
+    pub fn set(&mut self, index: usize, key: &str, value: &str) -> Result<(), EntityEditError> {
+        self.entity_mut(index)?.set(key, value)
+    }
+
+    pub fn append(&mut self, index: usize, key: &str, value: &str) -> Result<(), EntityEditError> {
+        self.entity_mut(index)?.append(key, value)
+    }
+
+    pub fn remove_key(&mut self, index: usize, key: &str) -> Result<usize, EntityEditError> {
+        Ok(self.entity_mut(index)?.remove(key))
+    }
+
+    pub fn add(&mut self, classname: &str) -> Result<usize, EntityEditError> {
+        let mut entity = Entity::new();
+        entity.set("classname", classname)?;
+        Ok(self.push(entity))
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<(), EntityEditError> {
+        if index >= self.entities.len() {
+            return Err(EntityEditError::UnexpectedIndex { found: index });
+        }
+
+        self.entities.remove(index);
+        Ok(())
+    }
+
+    pub fn find(&self, key: &str, value: &str) -> Vec<usize> {
+        self.entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| entity.contains(key, value))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn serialise(&self) -> Vec<u8> {
+        let mut out = self.prefix.clone();
+        for entity in &self.entities {
+            entity.write(&mut out);
+        }
+
+        out.extend(&self.suffix);
+        if !self.suffix.starts_with(b"\0") {
+            out.push(0);
+        }
+
+        out
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn entity_lump(&self) -> Result<EntityLump, EntitiesError> {
+        let buf = self.lump_of(LumpKind::Entities)?;
+        Ok(EntityLump::new(&buf)?)
+    }
+
+    pub fn set_entities(&mut self, entities: &EntityLump) -> Result<(), BSPError> {
+        let lzma = self.raw_lump(LumpKind::Entities as usize)?.starts_with(b"LZMA");
+        self.set_lump(LumpKind::Entities, entities.serialise(), lzma)
+    }
+}
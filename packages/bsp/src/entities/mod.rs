@@ -1,65 +1,51 @@
-use std::{collections::HashMap, collections::hash_map::Entry, vec};
-
 use serde::Serialize;
-use serde_with::{OneOrMany, formats::PreferOne, serde_as};
 use thiserror::Error;
 use tokeniser::{Token, Tokeniser};
 use tsify::Tsify;
 
+mod edit;
 mod tokeniser;
 
-#[serde_as]
+pub use edit::{Entity, EntityEditError, EntityInfo, EntityLump, KeyValue, KeyValues};
+pub use tokeniser::{Location, Span};
+
+/// Every entity in lump order, with the span of each entity, key and value.
 #[derive(Debug, Serialize, Tsify)]
-#[tsify(into_wasm_abi, hashmap_as_object)]
-pub struct Entities(
-    #[serde_as(as = "Vec<HashMap<_, OneOrMany<_, PreferOne>>>")]
-    #[tsify(type = "Array<Record<string, string | string[]>>")]
-    Vec<HashMap<String, Vec<String>>>,
-);
+#[tsify(into_wasm_abi)]
+pub struct Entities(pub Vec<EntityInfo>);
 
 #[derive(Debug, Error)]
 #[error("{:#?}", self)]
-pub struct SyntaxError;
-
-impl Entities {
-    pub fn new(str: String) -> Result<Self, SyntaxError> {
-        let mut tokens = Tokeniser { chars: str.chars() };
-        let mut entities = vec![];
-
-        loop {
-            let mut entity = HashMap::<String, Vec<String>>::new();
+pub struct SyntaxError {
+    pub location: Location,
+    pub found: Option<String>,
+}
 
-            match tokens.next() {
-                Some(Token::OpeningBrace) => loop {
-                    match tokens.next().ok_or(SyntaxError)? {
-                        Token::String(key) => {
-                            let value = tokens
-                                .next()
-                                .and_then(|token| match token {
-                                    Token::String(value) => Some(value),
-                                    _ => None,
-                                })
-                                .ok_or(SyntaxError)?;
+impl SyntaxError {
+    fn new(token: Option<(Token, Span)>, tokens: &Tokeniser) -> Self {
+        match token {
+            Some((token, span)) => SyntaxError {
+                location: span.start,
+                found: Some(format!("{:?}", token)),
+            },
+            None => SyntaxError {
+                location: tokens.location,
+                found: None,
+            },
+        }
+    }
+}
 
-                            match entity.entry(key) {
-                                Entry::Occupied(mut occupied) => occupied.get_mut().push(value),
-                                Entry::Vacant(vacant) => _ = vacant.insert(vec![value]),
-                            }
-                        }
-                        Token::ClosingBrace => break,
-                        _ => Err(SyntaxError)?,
-                    };
-                },
-                Some(Token::Eof) => break,
-                None => break,
-                _ => Err(SyntaxError)?,
-            };
+impl Entities {
+    pub fn new(buf: &[u8]) -> Result<Self, SyntaxError> {
+        Ok(EntityLump::new(buf)?.outline())
+    }
 
-            if !entity.is_empty() {
-                entities.push(entity);
-            }
-        }
+    pub fn iter(&self) -> impl Iterator<Item = &EntityInfo> {
+        self.0.iter()
+    }
 
-        Ok(Entities(entities))
+    pub fn find(&self, classname: &str) -> Option<&EntityInfo> {
+        self.0.iter().find(|entity| entity.get_all("classname").any(|value| value == classname))
     }
 }
@@ -1,34 +1,122 @@
-use std::{iter, str::Chars};
+use std::fmt;
+
+use serde::Serialize;
+use tsify::Tsify;
 
 const WHITESPACE: [char; 4] = [' ', '\t', '\r', '\n'];
 
+// Lines and columns start at 1. Columns count UTF-16 code units like editor positions do, offsets count bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Tsify)]
+pub struct Location {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Covers a whole token, including the quotes of a quoted string. `end` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
 pub struct Tokeniser<'a> {
-    pub chars: Chars<'a>,
+    bytes: &'a [u8],
+    pub location: Location,
 }
 
-#[derive(Debug)]
-pub enum Token {
+// Strings borrow the raw bytes between their quotes so edits can write them back unchanged.
+pub enum Token<'a> {
     OpeningBrace,
     ClosingBrace,
-    String(String),
+    String(&'a [u8]),
     Eof,
 }
 
-impl Iterator for Tokeniser<'_> {
-    type Item = Token;
+impl fmt::Debug for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::OpeningBrace => write!(f, "OpeningBrace"),
+            Token::ClosingBrace => write!(f, "ClosingBrace"),
+            Token::String(bytes) => write!(f, "String({:?})", String::from_utf8_lossy(bytes)),
+            Token::Eof => write!(f, "Eof"),
+        }
+    }
+}
+
+// Invalid UTF-8 decodes as U+FFFD but still advances by the bytes it covers, so offsets index the original lump.
+fn decode(bytes: &[u8]) -> Option<(char, usize)> {
+    let width = match *bytes.first()? {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+    .min(bytes.len());
+
+    match std::str::from_utf8(&bytes[..width]) {
+        Ok(str) => str.chars().next().map(|char| (char, width)),
+        Err(err) => Some((char::REPLACEMENT_CHARACTER, err.error_len().unwrap_or(width).max(1))),
+    }
+}
+
+impl<'a> Tokeniser<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Tokeniser {
+            bytes,
+            location: Location { offset: 0, line: 1, column: 1 },
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        decode(self.bytes.get(self.location.offset..)?).map(|(char, _)| char)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (char, len) = decode(self.bytes.get(self.location.offset..)?)?;
+        self.location.offset += len;
+
+        match char {
+            '\n' => {
+                self.location.line += 1;
+                self.location.column = 1;
+            }
+            _ => self.location.column += char.len_utf16(),
+        }
+
+        Some(char)
+    }
+
+    fn take_until(&mut self, start: usize, end: impl Fn(char) -> bool) -> &'a [u8] {
+        while self.peek().is_some_and(|char| !end(char)) {
+            self.bump();
+        }
+        &self.bytes[start..self.location.offset]
+    }
+}
+
+impl<'a> Iterator for Tokeniser<'a> {
+    type Item = (Token<'a>, Span);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.chars.by_ref().find(|char| !WHITESPACE.contains(char))? {
-            '\0' => Some(Token::Eof),
-            '{' => Some(Token::OpeningBrace),
-            '}' => Some(Token::ClosingBrace),
-            '"' => Some(Token::String(self.chars.by_ref().take_while(|char| *char != '"').collect::<String>())),
-            char => Some(Token::String(
-                iter::once(char)
-                    .chain(self.chars.by_ref())
-                    .take_while(|char| !WHITESPACE.contains(char))
-                    .collect::<String>(),
-            )),
+        while self.peek().is_some_and(|char| WHITESPACE.contains(&char)) {
+            self.bump();
         }
+
+        let start = self.location;
+        let token = match self.bump()? {
+            '\0' => Token::Eof,
+            '{' => Token::OpeningBrace,
+            '}' => Token::ClosingBrace,
+            '"' => {
+                let string = self.take_until(self.location.offset, |char| char == '"');
+                self.bump();
+                Token::String(string)
+            }
+            _ => Token::String(self.take_until(start.offset, |char| WHITESPACE.contains(&char))),
+        };
+
+        Some((token, Span { start, end: self.location }))
     }
 }
@@ -0,0 +1,159 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    bsp::{BSP, BSPError},
+    reader::Reader,
+};
+
+const MODEL_NAME_LEN: usize = 128;
+const DETAIL_PROP_LEN: usize = 52;
+const DEFAULT_SPRITE_MATERIAL: &str = "detail/detailsprites";
+
+#[derive(Debug, Default, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct DetailProps {
+    pub version: u16,
+    pub sprite_material: String,
+    pub models: Vec<String>,
+    pub sprites: Vec<DetailSprite>,
+    pub props: Vec<DetailProp>,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct DetailSprite {
+    pub upper_left: [f32; 2],
+    pub lower_right: [f32; 2],
+    pub tex_upper_left: [f32; 2],
+    pub tex_lower_right: [f32; 2],
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct DetailProp {
+    pub origin: [f32; 3],
+    pub angles: [f32; 3],
+    pub model: u16,
+    pub leaf: u16,
+    pub lighting: [u8; 4],
+    pub light_styles: u32,
+    pub light_style_count: u8,
+    pub sway_amount: u8,
+    pub shape_angle: u8,
+    pub shape_size: u8,
+    pub orientation: u8,
+    pub kind: DetailPropKind,
+    pub scale: f32,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[serde(tag = "type", content = "value")]
+pub enum DetailPropKind {
+    Model,
+    Sprite,
+    ShapeCross,
+    ShapeTri,
+    Unknown(u8),
+}
+
+impl DetailProp {
+    fn read(reader: &mut Reader, stride: usize) -> Result<DetailProp, BSPError> {
+        let start = reader.pos;
+
+        let origin = reader.vector()?;
+        let angles = reader.vector()?;
+        let model = reader.u16()?;
+        let leaf = reader.u16()?;
+        let lighting = reader.array()?;
+        let light_styles = reader.u32()?;
+        let [light_style_count, sway_amount, shape_angle, shape_size, orientation, _, _, _] = reader.array()?;
+        let [kind, _, _, _] = reader.array()?;
+        let scale = reader.f32()?;
+
+        reader.pos = start + stride;
+
+        Ok(DetailProp {
+            origin,
+            angles,
+            model,
+            leaf,
+            lighting,
+            light_styles,
+            light_style_count,
+            sway_amount,
+            shape_angle,
+            shape_size,
+            orientation,
+            kind: match kind {
+                0 => DetailPropKind::Model,
+                1 => DetailPropKind::Sprite,
+                2 => DetailPropKind::ShapeCross,
+                3 => DetailPropKind::ShapeTri,
+                kind => DetailPropKind::Unknown(kind),
+            },
+            scale,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn detail_props(&self) -> Result<DetailProps, BSPError> {
+        let sprite_material = self
+            .entities()
+            .ok()
+            .and_then(|entities| {
+                entities
+                    .find("worldspawn")
+                    .and_then(|worldspawn| worldspawn.get("detailmaterial"))
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| DEFAULT_SPRITE_MATERIAL.to_string());
+
+        let Some((game_lump, buf)) = self.game_lump("dprp")? else {
+            return Ok(DetailProps {
+                sprite_material,
+                ..Default::default()
+            });
+        };
+
+        let mut reader = Reader::new(&buf);
+
+        let model_count = reader.i32()?;
+        let models = (0..model_count).map(|_| reader.string(MODEL_NAME_LEN)).collect::<Result<Vec<String>, _>>()?;
+
+        let sprite_count = reader.i32()?;
+        let sprites = (0..sprite_count)
+            .map(|_| {
+                Ok(DetailSprite {
+                    upper_left: reader.vector2()?,
+                    lower_right: reader.vector2()?,
+                    tex_upper_left: reader.vector2()?,
+                    tex_lower_right: reader.vector2()?,
+                })
+            })
+            .collect::<Result<Vec<DetailSprite>, BSPError>>()?;
+
+        let prop_count = reader.i32()?.max(0) as usize;
+        let stride = reader.remaining().checked_div(prop_count).unwrap_or(0);
+        if prop_count > 0 && stride < DETAIL_PROP_LEN {
+            return Err(BSPError::UnexpectedGameLump {
+                id: "dprp".to_string(),
+                version: game_lump.version,
+                stride,
+            });
+        }
+
+        let props = (0..prop_count)
+            .map(|_| DetailProp::read(&mut reader, stride))
+            .collect::<Result<Vec<DetailProp>, BSPError>>()?;
+
+        Ok(DetailProps {
+            version: game_lump.version,
+            sprite_material,
+            models,
+            sprites,
+            props,
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    bsp::{BSP, BSPError, decompress},
+    lump::LumpKind,
+    reader::Reader,
+};
+
+mod detail_props;
+mod static_props;
+
+pub use detail_props::{DetailProp, DetailPropKind, DetailProps, DetailSprite};
+pub use static_props::{StaticProp, StaticProps};
+
+const COMPRESSED: u16 = 0x0001;
+
+#[derive(Debug, Clone, Serialize, Tsify)]
+pub struct GameLump {
+    pub id: String,
+    pub flags: u16,
+    pub version: u16,
+    pub offset: i32,
+    pub len: i32,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct GameLumps(pub Vec<GameLump>);
+
+impl BSP {
+    pub fn game_lump(&self, id: &str) -> Result<Option<(GameLump, Vec<u8>)>, BSPError> {
+        let GameLumps(game_lumps) = self.game_lumps()?;
+        let Some(index) = game_lumps.iter().position(|game_lump| game_lump.id == id) else {
+            return Ok(None);
+        };
+
+        let game_lump = &game_lumps[index];
+        let range = |offset: i32, len: i32| usize::try_from(offset).ok().zip(usize::try_from(len).ok());
+
+        let start = usize::try_from(game_lump.offset).ok();
+        let end = match game_lump.flags & COMPRESSED {
+            0 => range(game_lump.offset, game_lump.len).and_then(|(offset, len)| offset.checked_add(len)),
+            _ => match game_lumps.get(index + 1) {
+                Some(next) => usize::try_from(next.offset).ok(),
+                None => {
+                    let lump = &self.header.lumps[LumpKind::GameLump as usize];
+                    range(lump.offset, lump.len).and_then(|(offset, len)| offset.checked_add(len))
+                }
+            },
+        };
+        let (start, end) = start.zip(end).ok_or_else(|| BSPError::UnexpectedGameLumpRange {
+            id: game_lump.id.clone(),
+            offset: game_lump.offset,
+            len: game_lump.len,
+        })?;
+
+        let buf = self.buf.get(start..end).ok_or_else(|| bincode::error::DecodeError::UnexpectedEnd {
+            additional: end.saturating_sub(start),
+        })?;
+        let buf = match buf.get(0..4) {
+            Some(b"LZMA") => decompress(buf)?,
+            _ => buf.to_vec(),
+        };
+
+        Ok(Some((game_lump.clone(), buf)))
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn game_lumps(&self) -> Result<GameLumps, BSPError> {
+        let buf = self.lump_of(LumpKind::GameLump)?;
+        if buf.is_empty() {
+            return Ok(GameLumps(vec![]));
+        }
+
+        let mut reader = Reader::new(&buf);
+        let count = reader.i32()?;
+
+        let game_lumps = (0..count)
+            .map(|_| {
+                Ok(GameLump {
+                    id: String::from_utf8_lossy(&reader.u32()?.to_be_bytes()).into_owned(),
+                    flags: reader.u16()?,
+                    version: reader.u16()?,
+                    offset: reader.i32()?,
+                    len: reader.i32()?,
+                })
+            })
+            .collect::<Result<Vec<GameLump>, BSPError>>()?;
+
+        Ok(GameLumps(game_lumps))
+    }
+}
@@ -0,0 +1,146 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    bsp::{BSP, BSPError},
+    reader::Reader,
+};
+
+const MODEL_NAME_LEN: usize = 128;
+
+#[derive(Debug, Default, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct StaticProps {
+    pub version: u16,
+    pub models: Vec<String>,
+    pub leaves: Vec<u16>,
+    pub props: Vec<StaticProp>,
+}
+
+#[derive(Debug, Default, Serialize, Tsify)]
+pub struct StaticProp {
+    pub origin: [f32; 3],
+    pub angles: [f32; 3],
+    pub model: u16,
+    pub first_leaf: u16,
+    pub leaf_count: u16,
+    pub solid: u8,
+    pub flags: u8,
+    pub skin: i32,
+    pub fade_min_dist: f32,
+    pub fade_max_dist: f32,
+    pub lighting_origin: [f32; 3],
+    pub forced_fade_scale: Option<f32>,
+    pub dx_level: Option<[u16; 2]>,
+    pub cpu_level: Option<[u8; 2]>,
+    pub gpu_level: Option<[u8; 2]>,
+    pub diffuse_modulation: Option<[u8; 4]>,
+    pub disable_x360: Option<bool>,
+    pub flags_ex: Option<u32>,
+    pub lightmap_resolution: Option<[u16; 2]>,
+    pub uniform_scale: Option<f32>,
+}
+
+impl StaticProp {
+    fn read(reader: &mut Reader, version: u16, stride: usize) -> Result<StaticProp, BSPError> {
+        let start = reader.pos;
+
+        let mut prop = StaticProp {
+            origin: reader.vector()?,
+            angles: reader.vector()?,
+            model: reader.u16()?,
+            first_leaf: reader.u16()?,
+            leaf_count: reader.u16()?,
+            solid: reader.u8()?,
+            flags: reader.u8()?,
+            skin: reader.i32()?,
+            fade_min_dist: reader.f32()?,
+            fade_max_dist: reader.f32()?,
+            lighting_origin: reader.vector()?,
+            ..Default::default()
+        };
+
+        if version >= 5 {
+            prop.forced_fade_scale = Some(reader.f32()?);
+        }
+
+        match (version, stride) {
+            (4 | 5, _) => {}
+            (6, _) => prop.dx_level = Some([reader.u16()?, reader.u16()?]),
+            (7, 68) => {
+                prop.dx_level = Some([reader.u16()?, reader.u16()?]);
+                prop.diffuse_modulation = Some(reader.array()?);
+            }
+            (7 | 10, 72) => {
+                prop.dx_level = Some([reader.u16()?, reader.u16()?]);
+                prop.flags_ex = Some(reader.u32()?);
+                prop.lightmap_resolution = Some([reader.u16()?, reader.u16()?]);
+            }
+            (8..=11, _) => {
+                prop.cpu_level = Some([reader.u8()?, reader.u8()?]);
+                prop.gpu_level = Some([reader.u8()?, reader.u8()?]);
+                prop.diffuse_modulation = Some(reader.array()?);
+
+                if version >= 9 {
+                    prop.disable_x360 = Some(reader.u32()? != 0);
+                }
+                if version >= 10 {
+                    prop.flags_ex = Some(reader.u32()?);
+                }
+                if version >= 11 {
+                    prop.uniform_scale = Some(reader.f32()?);
+                }
+            }
+            _ => {
+                return Err(BSPError::UnexpectedGameLump {
+                    id: "sprp".to_string(),
+                    version,
+                    stride,
+                });
+            }
+        }
+
+        if reader.pos - start > stride {
+            return Err(BSPError::UnexpectedGameLump {
+                id: "sprp".to_string(),
+                version,
+                stride,
+            });
+        }
+
+        reader.pos = start + stride;
+        Ok(prop)
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn static_props(&self) -> Result<StaticProps, BSPError> {
+        let Some((game_lump, buf)) = self.game_lump("sprp")? else {
+            return Ok(StaticProps::default());
+        };
+
+        let mut reader = Reader::new(&buf);
+
+        let model_count = reader.i32()?;
+        let models = (0..model_count).map(|_| reader.string(MODEL_NAME_LEN)).collect::<Result<Vec<String>, _>>()?;
+
+        let leaf_count = reader.i32()?;
+        let leaves = (0..leaf_count).map(|_| reader.u16()).collect::<Result<Vec<u16>, _>>()?;
+
+        let prop_count = reader.i32()?.max(0) as usize;
+        let stride = reader.remaining().checked_div(prop_count).unwrap_or(0);
+
+        let props = (0..prop_count)
+            .map(|_| StaticProp::read(&mut reader, game_lump.version, stride))
+            .collect::<Result<Vec<StaticProp>, BSPError>>()?;
+
+        Ok(StaticProps {
+            version: game_lump.version,
+            models,
+            leaves,
+            props,
+        })
+    }
+}
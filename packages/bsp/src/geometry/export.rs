@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use serde_json::json;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::bsp::{BSP, BSPError};
+
+use super::MeshGroup;
+
+fn y_up(position: [f32; 3]) -> [f32; 3] {
+    [position[0], position[2], -position[1]]
+}
+
+impl MeshGroup {
+    fn triangles(&self) -> impl Iterator<Item = [u32; 3]> {
+        self.indices.chunks_exact(3).map(|triangle| [triangle[0], triangle[2], triangle[1]])
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn obj(&self) -> Result<String, BSPError> {
+        let mut obj = String::new();
+        let mut base = 1;
+
+        for mesh in self.meshes()? {
+            if mesh.positions.is_empty() {
+                continue;
+            }
+
+            _ = writeln!(obj, "o model_{}", mesh.model);
+            for [x, y, z] in mesh.positions.iter().map(|position| y_up(*position)) {
+                _ = writeln!(obj, "v {} {} {}", x, y, z);
+            }
+            for [u, v] in &mesh.uvs {
+                _ = writeln!(obj, "vt {} {}", u, 1.0 - v);
+            }
+            for [x, y, z] in mesh.normals.iter().map(|normal| y_up(*normal)) {
+                _ = writeln!(obj, "vn {} {} {}", x, y, z);
+            }
+
+            for group in &mesh.groups {
+                _ = writeln!(obj, "usemtl {}", group.material);
+                for triangle in group.triangles() {
+                    let [a, b, c] = triangle.map(|i| i + base);
+                    _ = writeln!(obj, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}");
+                }
+            }
+
+            base += mesh.positions.len() as u32;
+        }
+
+        Ok(obj)
+    }
+
+    pub fn glb(&self) -> Result<Vec<u8>, BSPError> {
+        let meshes = self.meshes()?;
+
+        let mut bin = Vec::<u8>::new();
+        let mut buffer_views = vec![];
+        let mut accessors = vec![];
+        let mut materials = Vec::<String>::new();
+        let mut gltf_meshes = vec![];
+        let mut nodes = vec![];
+
+        let mut view = |bin: &mut Vec<u8>, bytes: &[u8], target: u32| {
+            buffer_views.push(json!({ "buffer": 0, "byteOffset": bin.len(), "byteLength": bytes.len(), "target": target }));
+            bin.extend(bytes);
+            buffer_views.len() - 1
+        };
+
+        for mesh in meshes.iter().filter(|mesh| !mesh.positions.is_empty()) {
+            let positions = mesh.positions.iter().map(|position| y_up(*position)).collect::<Vec<[f32; 3]>>();
+            let (min, max) = positions.iter().fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), position| {
+                ([0, 1, 2].map(|i| min[i].min(position[i])), [0, 1, 2].map(|i| max[i].max(position[i])))
+            });
+
+            let attributes = [
+                positions.iter().flatten().flat_map(|value| value.to_le_bytes()).collect::<Vec<u8>>(),
+                mesh.normals
+                    .iter()
+                    .flat_map(|normal| y_up(*normal))
+                    .flat_map(|value| value.to_le_bytes())
+                    .collect(),
+                mesh.uvs.iter().flatten().flat_map(|value| value.to_le_bytes()).collect(),
+            ]
+            .map(|bytes| view(&mut bin, &bytes, 34962));
+
+            let first_accessor = accessors.len();
+            accessors.push(json!({ "bufferView": attributes[0], "componentType": 5126, "count": positions.len(), "type": "VEC3", "min": min, "max": max }));
+            accessors.push(json!({ "bufferView": attributes[1], "componentType": 5126, "count": positions.len(), "type": "VEC3" }));
+            accessors.push(json!({ "bufferView": attributes[2], "componentType": 5126, "count": positions.len(), "type": "VEC2" }));
+
+            let primitives = mesh
+                .groups
+                .iter()
+                .map(|group| {
+                    let indices = group.triangles().flatten().flat_map(|index| index.to_le_bytes()).collect::<Vec<u8>>();
+                    let index_view = view(&mut bin, &indices, 34963);
+
+                    accessors.push(json!({ "bufferView": index_view, "componentType": 5125, "count": indices.len() / 4, "type": "SCALAR" }));
+
+                    let material = match materials.iter().position(|material| *material == group.material) {
+                        Some(material) => material,
+                        None => {
+                            materials.push(group.material.clone());
+                            materials.len() - 1
+                        }
+                    };
+
+                    json!({
+                        "attributes": { "POSITION": first_accessor, "NORMAL": first_accessor + 1, "TEXCOORD_0": first_accessor + 2 },
+                        "indices": accessors.len() - 1,
+                        "material": material,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            nodes.push(json!({ "name": format!("model_{}", mesh.model), "mesh": gltf_meshes.len() }));
+            gltf_meshes.push(json!({ "name": format!("model_{}", mesh.model), "primitives": primitives }));
+        }
+
+        let gltf = json!({
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": (0..nodes.len()).collect::<Vec<usize>>() }],
+            "nodes": nodes,
+            "meshes": gltf_meshes,
+            "materials": materials.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+            "accessors": accessors,
+            "bufferViews": buffer_views,
+            "buffers": [{ "byteLength": bin.len() }],
+        });
+
+        let mut json = gltf.to_string().into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let mut glb = Vec::with_capacity(12 + 8 + json.len() + 8 + bin.len());
+        glb.extend(b"glTF");
+        glb.extend(2u32.to_le_bytes());
+        glb.extend(((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        glb.extend((json.len() as u32).to_le_bytes());
+        glb.extend(b"JSON");
+        glb.extend(json);
+        glb.extend((bin.len() as u32).to_le_bytes());
+        glb.extend(b"BIN\0");
+        glb.extend(bin);
+
+        Ok(glb)
+    }
+}
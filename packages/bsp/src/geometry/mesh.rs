@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use crate::bsp::{BSP, BSPError};
+
+use super::{DispInfo, DispVert, Face, TexInfo};
+
+const SURF_NODRAW: i32 = 0x0080;
+const SURF_HINT: i32 = 0x0100;
+const SURF_SKIP: i32 = 0x0200;
+
+#[derive(Debug, Default)]
+pub struct Mesh {
+    pub model: usize,
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub uvs: Vec<[f32; 2]>,
+    pub groups: Vec<MeshGroup>,
+}
+
+#[derive(Debug)]
+pub struct MeshGroup {
+    pub material: String,
+    pub indices: Vec<u32>,
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    add(a, scale(sub(b, a), t))
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn normalise(a: [f32; 3]) -> [f32; 3] {
+    scale(a, 1.0 / dot(a, a).sqrt().max(f32::EPSILON))
+}
+
+struct World {
+    vertices: Vec<[f32; 3]>,
+    edges: Vec<[u16; 2]>,
+    surfedges: Vec<i32>,
+    faces: Vec<Face>,
+    planes: Vec<[f32; 3]>,
+    texinfo: Vec<TexInfo>,
+    texdata: Vec<(String, [f32; 2])>,
+    disp_info: Vec<DispInfo>,
+    disp_verts: Vec<DispVert>,
+}
+
+impl World {
+    fn face_vertices(&self, face: &Face) -> Vec<[f32; 3]> {
+        (face.first_edge..face.first_edge + face.edge_count as i32)
+            .filter_map(|i| {
+                let surfedge = *self.surfedges.get(i as usize)?;
+                let edge = self.edges.get(surfedge.unsigned_abs() as usize)?;
+                self.vertices.get(edge[(surfedge < 0) as usize] as usize).copied()
+            })
+            .collect()
+    }
+
+    fn uv(&self, texinfo: &TexInfo, size: [f32; 2], position: [f32; 3]) -> [f32; 2] {
+        let [s, t] = texinfo.texture_vecs.map(|vec| dot(position, [vec[0], vec[1], vec[2]]) + vec[3]);
+        [s / size[0].max(1.0), t / size[1].max(1.0)]
+    }
+}
+
+struct MeshBuilder {
+    mesh: Mesh,
+    groups: HashMap<String, usize>,
+}
+
+impl MeshBuilder {
+    fn group(&mut self, material: &str) -> usize {
+        *self.groups.entry(material.to_string()).or_insert_with(|| {
+            self.mesh.groups.push(MeshGroup {
+                material: material.to_string(),
+                indices: vec![],
+            });
+            self.mesh.groups.len() - 1
+        })
+    }
+
+    fn face(&mut self, world: &World, face: &Face) -> Result<(), BSPError> {
+        let Some(texinfo) = world.texinfo.get(face.texinfo as usize) else {
+            return Ok(());
+        };
+        if texinfo.flags & (SURF_NODRAW | SURF_HINT | SURF_SKIP) != 0 {
+            return Ok(());
+        }
+        let (material, size) = world.texdata.get(texinfo.texdata as usize).cloned().unwrap_or_default();
+
+        let vertices = world.face_vertices(face);
+        if vertices.len() < 3 {
+            return Ok(());
+        }
+
+        match world.disp_info.get(face.dispinfo as usize).filter(|_| face.dispinfo >= 0) {
+            Some(disp_info) => self.displacement(world, texinfo, &material, size, &vertices, disp_info)?,
+            None => {
+                let normal = world.planes.get(face.plane as usize).copied().unwrap_or([0.0, 0.0, 1.0]);
+                let normal = if face.side != 0 { scale(normal, -1.0) } else { normal };
+
+                let base = self.mesh.positions.len() as u32;
+                for position in &vertices {
+                    self.mesh.positions.push(*position);
+                    self.mesh.normals.push(normal);
+                    self.mesh.uvs.push(world.uv(texinfo, size, *position));
+                }
+
+                let group = self.group(&material);
+                for i in 1..vertices.len() as u32 - 1 {
+                    self.mesh.groups[group].indices.extend([base, base + i, base + i + 1]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn displacement(
+        &mut self,
+        world: &World,
+        texinfo: &TexInfo,
+        material: &str,
+        size: [f32; 2],
+        corners: &[[f32; 3]],
+        disp_info: &DispInfo,
+    ) -> Result<(), BSPError> {
+        if corners.len() != 4 {
+            return Ok(());
+        }
+        if !(2..=4).contains(&disp_info.power) {
+            return Err(BSPError::UnexpectedDispPower { found: disp_info.power });
+        }
+
+        let start = (0..4)
+            .min_by(|a, b| {
+                let distance = |i: usize| {
+                    let delta = sub(corners[i], disp_info.start_position);
+                    dot(delta, delta)
+                };
+                distance(*a).total_cmp(&distance(*b))
+            })
+            .unwrap_or(0);
+        let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| corners[(start + i) % 4]);
+
+        let side = (1 << disp_info.power) + 1;
+        let base = self.mesh.positions.len() as u32;
+        let first_vertex = usize::try_from(disp_info.first_vertex).ok();
+
+        for y in 0..side {
+            let t = y as f32 / (side - 1) as f32;
+            let (a, b) = (lerp(p0, p1, t), lerp(p3, p2, t));
+
+            for x in 0..side {
+                let flat = lerp(a, b, x as f32 / (side - 1) as f32);
+                let vert = first_vertex
+                    .and_then(|first| first.checked_add(y * side + x))
+                    .and_then(|i| world.disp_verts.get(i));
+                let position = match vert {
+                    Some(vert) => add(flat, scale(vert.offset, vert.dist)),
+                    None => flat,
+                };
+
+                self.mesh.positions.push(position);
+                self.mesh.normals.push([0.0; 3]);
+                self.mesh.uvs.push(world.uv(texinfo, size, flat));
+            }
+        }
+
+        let group = self.group(material);
+        for y in 0..side as u32 - 1 {
+            for x in 0..side as u32 - 1 {
+                let i = base + y * side as u32 + x;
+                let (right, below) = (i + 1, i + side as u32);
+                let quad = match (x + y) % 2 {
+                    0 => [i, below + 1, right, i, below, below + 1],
+                    _ => [i, below, right, right, below, below + 1],
+                };
+
+                for triangle in quad.chunks_exact(3) {
+                    let [a, b, c] = [0, 1, 2].map(|j| self.mesh.positions[triangle[j] as usize]);
+                    let normal = cross(sub(c, a), sub(b, a));
+                    for index in triangle {
+                        let normals = &mut self.mesh.normals[*index as usize];
+                        *normals = add(*normals, normal);
+                    }
+                }
+
+                self.mesh.groups[group].indices.extend(quad);
+            }
+        }
+
+        for normal in &mut self.mesh.normals[base as usize..] {
+            *normal = normalise(*normal);
+        }
+
+        Ok(())
+    }
+}
+
+impl BSP {
+    pub fn meshes(&self) -> Result<Vec<Mesh>, BSPError> {
+        let strings = self.texdata_strings()?;
+
+        let world = World {
+            vertices: self.vertices()?,
+            edges: self.edges()?.into_iter().map(|edge| edge.vertices).collect(),
+            surfedges: self.surfedges()?,
+            faces: self.faces()?,
+            planes: self.planes()?.into_iter().map(|plane| plane.normal).collect(),
+            texinfo: self.texinfo()?,
+            texdata: self
+                .texdata()?
+                .into_iter()
+                .map(|texdata| {
+                    let name = strings.get(texdata.name as usize).cloned().unwrap_or_default();
+                    (name, [texdata.width as f32, texdata.height as f32])
+                })
+                .collect(),
+            disp_info: self.disp_info()?,
+            disp_verts: self.disp_verts()?,
+        };
+
+        self.models()?
+            .iter()
+            .enumerate()
+            .map(|(index, model)| {
+                let mut builder = MeshBuilder {
+                    mesh: Mesh {
+                        model: index,
+                        ..Default::default()
+                    },
+                    groups: HashMap::new(),
+                };
+
+                let first = model.first_face.max(0) as usize;
+                for face in world.faces.iter().skip(first).take(model.face_count.max(0) as usize) {
+                    builder.face(&world, face)?;
+                }
+
+                Ok(builder.mesh)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,180 @@
+use bincode::Decode;
+
+use crate::{
+    bsp::{BSP, BSPError},
+    lump::LumpKind,
+};
+
+mod export;
+mod mesh;
+
+pub use mesh::{Mesh, MeshGroup};
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct Plane {
+    pub normal: [f32; 3],
+    pub dist: f32,
+    pub kind: i32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct Edge {
+    pub vertices: [u16; 2],
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct Face {
+    pub plane: u16,
+    pub side: u8,
+    pub on_node: u8,
+    pub first_edge: i32,
+    pub edge_count: i16,
+    pub texinfo: i16,
+    pub dispinfo: i16,
+    pub surface_fog_volume: i16,
+    pub styles: [u8; 4],
+    pub light_offset: i32,
+    pub area: f32,
+    pub lightmap_texture_mins: [i32; 2],
+    pub lightmap_texture_size: [i32; 2],
+    pub original_face: i32,
+    pub primitive_count: u16,
+    pub first_primitive: u16,
+    pub smoothing_groups: u32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct TexInfo {
+    pub texture_vecs: [[f32; 4]; 2],
+    pub lightmap_vecs: [[f32; 4]; 2],
+    pub flags: i32,
+    pub texdata: i32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct TexData {
+    pub reflectivity: [f32; 3],
+    pub name: i32,
+    pub width: i32,
+    pub height: i32,
+    pub view_width: i32,
+    pub view_height: i32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct Model {
+    pub mins: [f32; 3],
+    pub maxs: [f32; 3],
+    pub origin: [f32; 3],
+    pub head_node: i32,
+    pub first_face: i32,
+    pub face_count: i32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct DispInfo {
+    pub start_position: [f32; 3],
+    pub first_vertex: i32,
+    pub first_triangle: i32,
+    pub power: i32,
+    pub min_tesselation: i32,
+    pub smoothing_angle: f32,
+    pub contents: i32,
+    pub face: u16,
+    _neighbours: [u8; 138],
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct DispVert {
+    pub offset: [f32; 3],
+    pub dist: f32,
+    pub alpha: f32,
+}
+
+#[derive(Debug, Clone, Copy, Decode)]
+pub struct Overlay {
+    pub id: i32,
+    pub texinfo: i16,
+    pub face_count_and_render_order: u16,
+    pub faces: [i32; 64],
+    pub u: [f32; 2],
+    pub v: [f32; 2],
+    pub uv_points: [[f32; 3]; 4],
+    pub origin: [f32; 3],
+    pub normal: [f32; 3],
+}
+
+impl BSP {
+    pub fn decode_lump<T: Decode<()>>(&self, kind: LumpKind) -> Result<Vec<T>, BSPError> {
+        let buf = self.lump_of(kind)?;
+        let config = bincode::config::standard().with_fixed_int_encoding();
+
+        let mut items = vec![];
+        let mut offset = 0;
+        while offset < buf.len() {
+            let (item, read) = bincode::decode_from_slice(&buf[offset..], config)?;
+            items.push(item);
+            offset += read;
+        }
+
+        Ok(items)
+    }
+
+    pub fn planes(&self) -> Result<Vec<Plane>, BSPError> {
+        self.decode_lump(LumpKind::Planes)
+    }
+
+    pub fn vertices(&self) -> Result<Vec<[f32; 3]>, BSPError> {
+        self.decode_lump(LumpKind::Vertexes)
+    }
+
+    pub fn edges(&self) -> Result<Vec<Edge>, BSPError> {
+        self.decode_lump(LumpKind::Edges)
+    }
+
+    pub fn surfedges(&self) -> Result<Vec<i32>, BSPError> {
+        self.decode_lump(LumpKind::SurfEdges)
+    }
+
+    pub fn faces(&self) -> Result<Vec<Face>, BSPError> {
+        self.decode_lump(LumpKind::Faces)
+    }
+
+    pub fn texinfo(&self) -> Result<Vec<TexInfo>, BSPError> {
+        self.decode_lump(LumpKind::TexInfo)
+    }
+
+    pub fn texdata(&self) -> Result<Vec<TexData>, BSPError> {
+        self.decode_lump(LumpKind::TexData)
+    }
+
+    pub fn models(&self) -> Result<Vec<Model>, BSPError> {
+        self.decode_lump(LumpKind::Models)
+    }
+
+    pub fn disp_info(&self) -> Result<Vec<DispInfo>, BSPError> {
+        self.decode_lump(LumpKind::DispInfo)
+    }
+
+    pub fn disp_verts(&self) -> Result<Vec<DispVert>, BSPError> {
+        self.decode_lump(LumpKind::DispVerts)
+    }
+
+    pub fn overlays(&self) -> Result<Vec<Overlay>, BSPError> {
+        self.decode_lump(LumpKind::Overlays)
+    }
+
+    pub fn texdata_strings(&self) -> Result<Vec<String>, BSPError> {
+        let data = self.lump_of(LumpKind::TexDataStringData)?;
+        let table: Vec<i32> = self.decode_lump(LumpKind::TexDataStringTable)?;
+
+        Ok(table
+            .iter()
+            .map(|offset| {
+                let bytes = data.get(*offset as usize..).unwrap_or_default();
+                let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+                String::from_utf8_lossy(&bytes[..end]).into_owned()
+            })
+            .collect())
+    }
+}
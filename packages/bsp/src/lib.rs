@@ -1,9 +1,19 @@
-#![feature(string_from_utf8_lossy_owned)]
-
+mod assets;
 mod bsp;
 mod entities;
+mod gamelump;
+mod geometry;
+mod lump;
+mod materials;
 mod pakfile;
+mod reader;
+mod writer;
 
-pub use bsp::BSP;
-pub use entities::Entities;
-pub use pakfile::Pakfile;
+pub use assets::{Asset, AssetKind, Assets};
+pub use bsp::{BSP, BSPHeader, Lump, LumpOrder};
+pub use entities::{Entities, Entity, EntityEditError, EntityInfo, EntityLump, KeyValue, KeyValues, Location, Span};
+pub use gamelump::{DetailProp, DetailPropKind, DetailProps, DetailSprite, GameLump, GameLumps, StaticProp, StaticProps};
+pub use geometry::{DispInfo, DispVert, Edge, Face, Mesh, MeshGroup, Model, Overlay, Plane, TexData, TexInfo};
+pub use lump::{LumpInfo, LumpKind, LumpTable};
+pub use materials::{MaterialUsage, Materials};
+pub use pakfile::{PakCompression, Pakfile};
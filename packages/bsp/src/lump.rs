@@ -0,0 +1,221 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::bsp::{BSP, BSPError, BSPLZMAHeader};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LumpKind {
+    Entities,
+    Planes,
+    TexData,
+    Vertexes,
+    Visibility,
+    Nodes,
+    TexInfo,
+    Faces,
+    Lighting,
+    Occlusion,
+    Leafs,
+    FaceIds,
+    Edges,
+    SurfEdges,
+    Models,
+    WorldLights,
+    LeafFaces,
+    LeafBrushes,
+    Brushes,
+    BrushSides,
+    Areas,
+    AreaPortals,
+    Unused0,
+    Unused1,
+    Unused2,
+    Unused3,
+    DispInfo,
+    OriginalFaces,
+    PhysDisp,
+    PhysCollide,
+    VertNormals,
+    VertNormalIndices,
+    DispLightmapAlphas,
+    DispVerts,
+    DispLightmapSamplePositions,
+    GameLump,
+    LeafWaterData,
+    Primitives,
+    PrimVerts,
+    PrimIndices,
+    Pakfile,
+    ClipPortalVerts,
+    Cubemaps,
+    TexDataStringData,
+    TexDataStringTable,
+    Overlays,
+    LeafMinDistToWater,
+    FaceMacroTextureInfo,
+    DispTris,
+    PhysCollideSurface,
+    WaterOverlays,
+    LeafAmbientIndexHDR,
+    LeafAmbientIndex,
+    LightingHDR,
+    WorldLightsHDR,
+    LeafAmbientLightingHDR,
+    LeafAmbientLighting,
+    XZipPakfile,
+    FacesHDR,
+    MapFlags,
+    OverlayFades,
+    OverlaySystemLevels,
+    PhysLevel,
+    DispMultiBlend,
+}
+
+impl LumpKind {
+    pub const LUMPS: [LumpKind; 64] = [
+        LumpKind::Entities,
+        LumpKind::Planes,
+        LumpKind::TexData,
+        LumpKind::Vertexes,
+        LumpKind::Visibility,
+        LumpKind::Nodes,
+        LumpKind::TexInfo,
+        LumpKind::Faces,
+        LumpKind::Lighting,
+        LumpKind::Occlusion,
+        LumpKind::Leafs,
+        LumpKind::FaceIds,
+        LumpKind::Edges,
+        LumpKind::SurfEdges,
+        LumpKind::Models,
+        LumpKind::WorldLights,
+        LumpKind::LeafFaces,
+        LumpKind::LeafBrushes,
+        LumpKind::Brushes,
+        LumpKind::BrushSides,
+        LumpKind::Areas,
+        LumpKind::AreaPortals,
+        LumpKind::Unused0,
+        LumpKind::Unused1,
+        LumpKind::Unused2,
+        LumpKind::Unused3,
+        LumpKind::DispInfo,
+        LumpKind::OriginalFaces,
+        LumpKind::PhysDisp,
+        LumpKind::PhysCollide,
+        LumpKind::VertNormals,
+        LumpKind::VertNormalIndices,
+        LumpKind::DispLightmapAlphas,
+        LumpKind::DispVerts,
+        LumpKind::DispLightmapSamplePositions,
+        LumpKind::GameLump,
+        LumpKind::LeafWaterData,
+        LumpKind::Primitives,
+        LumpKind::PrimVerts,
+        LumpKind::PrimIndices,
+        LumpKind::Pakfile,
+        LumpKind::ClipPortalVerts,
+        LumpKind::Cubemaps,
+        LumpKind::TexDataStringData,
+        LumpKind::TexDataStringTable,
+        LumpKind::Overlays,
+        LumpKind::LeafMinDistToWater,
+        LumpKind::FaceMacroTextureInfo,
+        LumpKind::DispTris,
+        LumpKind::PhysCollideSurface,
+        LumpKind::WaterOverlays,
+        LumpKind::LeafAmbientIndexHDR,
+        LumpKind::LeafAmbientIndex,
+        LumpKind::LightingHDR,
+        LumpKind::WorldLightsHDR,
+        LumpKind::LeafAmbientLightingHDR,
+        LumpKind::LeafAmbientLighting,
+        LumpKind::XZipPakfile,
+        LumpKind::FacesHDR,
+        LumpKind::MapFlags,
+        LumpKind::OverlayFades,
+        LumpKind::OverlaySystemLevels,
+        LumpKind::PhysLevel,
+        LumpKind::DispMultiBlend,
+    ];
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct LumpTable(pub Vec<LumpInfo>);
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct LumpInfo {
+    pub index: usize,
+    pub kind: String,
+    pub offset: i32,
+    pub len: i32,
+    pub version: i32,
+    pub four_cc: u32,
+    pub compressed: bool,
+    pub uncompressed_len: usize,
+    pub error: Option<String>,
+}
+
+impl BSP {
+    pub fn raw_lump(&self, i: usize) -> Result<&[u8], BSPError> {
+        let lump = self.header.lumps.get(i).ok_or(BSPError::UnexpectedLump { found: i })?;
+
+        usize::try_from(lump.offset)
+            .ok()
+            .zip(usize::try_from(lump.len).ok())
+            .and_then(|(offset, len)| self.buf.get(offset..offset.checked_add(len)?))
+            .ok_or(BSPError::UnexpectedLumpRange {
+                index: i,
+                offset: lump.offset,
+                len: lump.len,
+            })
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn lump_of(&self, kind: LumpKind) -> Result<Vec<u8>, BSPError> {
+        self.lump(kind as usize)
+    }
+
+    pub fn lumps(&self) -> LumpTable {
+        let config = bincode::config::standard().with_fixed_int_encoding();
+
+        let lumps = LumpKind::LUMPS
+            .iter()
+            .zip(&self.header.lumps)
+            .enumerate()
+            .map(|(index, (kind, lump))| {
+                let sizes = self.raw_lump(index).and_then(|buf| {
+                    let lzma = match buf.get(0..4) {
+                        Some(b"LZMA") => Some(bincode::decode_from_slice::<BSPLZMAHeader, _>(buf, config)?.0),
+                        _ => None,
+                    };
+                    Ok((lzma.is_some(), lzma.map_or(buf.len(), |lzma| lzma.actual_size as usize)))
+                });
+
+                let (compressed, uncompressed_len, error) = match sizes {
+                    Ok((compressed, uncompressed_len)) => (compressed, uncompressed_len, None),
+                    Err(err) => (false, 0, Some(format!("{:?}", err))),
+                };
+
+                LumpInfo {
+                    index,
+                    kind: format!("{:?}", kind),
+                    offset: lump.offset,
+                    len: lump.len,
+                    version: lump.version,
+                    four_cc: u32::from_le_bytes(lump.four_cc),
+                    compressed,
+                    uncompressed_len,
+                    error,
+                }
+            })
+            .collect();
+
+        LumpTable(lumps)
+    }
+}
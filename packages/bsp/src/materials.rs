@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::bsp::{BSP, BSPError};
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct Materials(pub Vec<MaterialUsage>);
+
+#[derive(Debug, Default, Serialize, Tsify)]
+pub struct MaterialUsage {
+    pub name: String,
+    pub path: String,
+    pub faces: usize,
+    pub overlays: usize,
+}
+
+// Lowercase, forward slashes only, without surrounding whitespace or empty segments.
+pub(crate) fn normalise(path: &str) -> String {
+    path.trim()
+        .replace('\\', "/")
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("/")
+        .to_lowercase()
+}
+
+fn usage<'a>(materials: &'a mut BTreeMap<String, MaterialUsage>, name: &str) -> &'a mut MaterialUsage {
+    materials.entry(name.to_string()).or_insert_with(|| MaterialUsage {
+        name: name.to_string(),
+        path: format!("materials/{}.vmt", name),
+        ..Default::default()
+    })
+}
+
+impl BSP {
+    fn texinfo_materials(&self) -> Result<Vec<Option<String>>, BSPError> {
+        let strings = self.texdata_strings()?;
+        let texdata = self.texdata()?;
+
+        Ok(self
+            .texinfo()?
+            .iter()
+            .map(|texinfo| {
+                let texdata = texdata.get(texinfo.texdata as usize)?;
+                strings.get(texdata.name as usize).map(|name| normalise(name))
+            })
+            .collect())
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    pub fn materials(&self) -> Result<Materials, BSPError> {
+        let texinfo = self.texinfo_materials()?;
+
+        let mut materials = BTreeMap::<String, MaterialUsage>::new();
+        for name in self.texdata_strings()? {
+            usage(&mut materials, &normalise(&name));
+        }
+
+        for face in self.faces()? {
+            if let Some(Some(name)) = texinfo.get(face.texinfo as usize) {
+                usage(&mut materials, name).faces += 1;
+            }
+        }
+
+        for overlay in self.overlays()? {
+            if let Some(Some(name)) = texinfo.get(overlay.texinfo as usize) {
+                usage(&mut materials, name).overlays += 1;
+            }
+        }
+
+        Ok(Materials(materials.into_values().collect()))
+    }
+}
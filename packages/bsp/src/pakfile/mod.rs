@@ -8,13 +8,23 @@ use serde::Serialize;
 use thiserror::Error;
 use tsify::Tsify;
 use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
+use writer::{PakEntry, write_zip};
 use zip::{ZipArchive, result::ZipError};
 
+mod writer;
+
 #[wasm_bindgen]
 pub struct Pakfile {
     archive: ZipArchive<Cursor<Vec<u8>>>,
 }
 
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PakCompression {
+    Store,
+    Lzma,
+}
+
 #[derive(Debug, Serialize, Tsify)]
 #[tsify(into_wasm_abi)]
 pub struct Files(HashMap<String, BSPEntry>);
@@ -34,6 +44,12 @@ pub enum PakError {
     IO(#[from] io::Error),
     #[error("Invalid name")]
     InvalidName,
+    #[error("File not found: {0}")]
+    NotFound(String),
+    #[error("File already exists: {0}")]
+    AlreadyExists(String),
+    #[error("Too large for a zip archive")]
+    TooLarge,
 }
 
 impl From<PakError> for JsValue {
@@ -42,11 +58,34 @@ impl From<PakError> for JsValue {
     }
 }
 
+fn normalise(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches('/').to_string()
+}
+
+fn validate(path: &str) -> Result<String, PakError> {
+    let path = normalise(path);
+    match path.split('/').all(|segment| !matches!(segment, "" | "." | "..")) {
+        true => Ok(path),
+        false => Err(PakError::InvalidName),
+    }
+}
+
+fn find(entries: &[PakEntry], path: &str) -> Option<usize> {
+    entries.iter().position(|entry| entry.name.eq_ignore_ascii_case(path))
+}
+
 impl Pakfile {
     pub fn new(buf: Vec<u8>) -> Result<Pakfile, PakError> {
         let archive = ZipArchive::new(Cursor::new(buf))?;
         Ok(Pakfile { archive })
     }
+
+    fn edit(&mut self, f: impl FnOnce(&mut Vec<PakEntry>) -> Result<(), PakError>) -> Result<(), PakError> {
+        let mut entries = PakEntry::read_all(&mut self.archive)?;
+        f(&mut entries)?;
+        self.archive = ZipArchive::new(Cursor::new(write_zip(&entries)?))?;
+        Ok(())
+    }
 }
 
 #[wasm_bindgen]
@@ -97,6 +136,48 @@ impl Pakfile {
         Ok(Files(map))
     }
 
+    pub fn paths(&self) -> Vec<String> {
+        self.archive.file_names().map(|name| name.replace('\\', "/").to_lowercase()).collect()
+    }
+
+    pub fn add(&mut self, path: &str, data: Vec<u8>, compression: PakCompression) -> Result<(), PakError> {
+        let path = validate(path)?;
+        let entry = PakEntry::new(path.clone(), data, compression == PakCompression::Lzma)?;
+        self.edit(|entries| {
+            match find(entries, &path) {
+                Some(i) => entries[i] = entry,
+                None => entries.push(entry),
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove(&mut self, path: &str) -> Result<(), PakError> {
+        let path = normalise(path);
+        self.edit(|entries| {
+            let i = find(entries, &path).ok_or(PakError::NotFound(path))?;
+            entries.remove(i);
+            Ok(())
+        })
+    }
+
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), PakError> {
+        let (from, to) = (normalise(from), validate(to)?);
+
+        self.edit(|entries| {
+            let i = find(entries, &from).ok_or(PakError::NotFound(from))?;
+            if find(entries, &to).is_some_and(|j| j != i) {
+                return Err(PakError::AlreadyExists(to));
+            }
+            entries[i].name = to;
+            Ok(())
+        })
+    }
+
+    pub fn save(&mut self) -> Result<Vec<u8>, PakError> {
+        write_zip(&PakEntry::read_all(&mut self.archive)?)
+    }
+
     pub fn read(&mut self, i: usize) -> Result<Vec<u8>, PakError> {
         let mut file = self.archive.by_index(i)?;
         let mut buf = vec![0; file.size() as usize];
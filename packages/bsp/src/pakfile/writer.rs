@@ -0,0 +1,160 @@
+use std::io::{Cursor, Read};
+
+use zip::{CompressionMethod, DateTime, ZipArchive};
+
+use crate::bsp::compress;
+
+use super::PakError;
+
+const STORE: u16 = 0;
+const LZMA: u16 = 14;
+const LZMA_VERSION: [u8; 2] = [9, 20];
+
+#[derive(Debug)]
+pub(crate) struct PakEntry {
+    pub name: String,
+    pub method: u16,
+    pub crc32: u32,
+    pub size: u32,
+    pub time: u16,
+    pub date: u16,
+    pub data: Vec<u8>,
+}
+
+impl PakEntry {
+    pub fn new(name: String, data: Vec<u8>, lzma: bool) -> Result<PakEntry, PakError> {
+        let (time, date) = (DateTime::default().timepart(), DateTime::default().datepart());
+        let crc32 = crc32fast::hash(&data);
+        let size = u32::try_from(data.len()).map_err(|_| PakError::TooLarge)?;
+
+        if lzma {
+            let (properties, stream) = compress(&data)?;
+            // Like bspzip, only keep the compressed data when it is actually smaller.
+            if 4 + properties.len() + stream.len() < data.len() {
+                let mut compressed = Vec::with_capacity(4 + properties.len() + stream.len());
+                compressed.extend(LZMA_VERSION);
+                compressed.extend((properties.len() as u16).to_le_bytes());
+                compressed.extend(properties);
+                compressed.extend(stream);
+
+                return Ok(PakEntry {
+                    name,
+                    method: LZMA,
+                    crc32,
+                    size,
+                    time,
+                    date,
+                    data: compressed,
+                });
+            }
+        }
+
+        Ok(PakEntry {
+            name,
+            method: STORE,
+            crc32,
+            size,
+            time,
+            date,
+            data,
+        })
+    }
+
+    pub fn read_all(archive: &mut ZipArchive<Cursor<Vec<u8>>>) -> Result<Vec<PakEntry>, PakError> {
+        let mut entries = Vec::with_capacity(archive.len());
+
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i)?;
+            if !file.is_file() {
+                continue;
+            }
+
+            let name = file.name().to_string();
+            let modified = file.last_modified().unwrap_or_default();
+
+            // The engine only reads stored and LZMA entries, so anything else is stored uncompressed.
+            let (method, crc32, mut file) = match file.compression() {
+                CompressionMethod::Stored => (STORE, file.crc32(), file),
+                CompressionMethod::Lzma => (LZMA, file.crc32(), file),
+                _ => {
+                    drop(file);
+                    let file = archive.by_index(i)?;
+                    (STORE, file.crc32(), file)
+                }
+            };
+
+            let size = u32::try_from(file.size()).map_err(|_| PakError::TooLarge)?;
+            let mut data = Vec::with_capacity(file.compressed_size() as usize);
+            file.read_to_end(&mut data)?;
+
+            entries.push(PakEntry {
+                name,
+                method,
+                crc32,
+                size,
+                time: modified.timepart(),
+                date: modified.datepart(),
+                data,
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
+fn u16_len(len: usize) -> Result<u16, PakError> {
+    u16::try_from(len).map_err(|_| PakError::TooLarge)
+}
+
+fn u32_len(len: usize) -> Result<u32, PakError> {
+    u32::try_from(len).map_err(|_| PakError::TooLarge)
+}
+
+pub(crate) fn write_zip(entries: &[PakEntry]) -> Result<Vec<u8>, PakError> {
+    let mut buf = vec![];
+    let mut central = vec![];
+    let count = u16_len(entries.len())?;
+
+    for entry in entries {
+        let offset = u32_len(buf.len())?;
+        let (compressed_size, name_len) = (u32_len(entry.data.len())?, u16_len(entry.name.len())?);
+        let fields = |buf: &mut Vec<u8>| {
+            buf.extend(20u16.to_le_bytes());
+            buf.extend(0u16.to_le_bytes());
+            buf.extend(entry.method.to_le_bytes());
+            buf.extend(entry.time.to_le_bytes());
+            buf.extend(entry.date.to_le_bytes());
+            buf.extend(entry.crc32.to_le_bytes());
+            buf.extend(compressed_size.to_le_bytes());
+            buf.extend(entry.size.to_le_bytes());
+            buf.extend(name_len.to_le_bytes());
+            buf.extend(0u16.to_le_bytes());
+        };
+
+        buf.extend(0x04034b50u32.to_le_bytes());
+        fields(&mut buf);
+        buf.extend(entry.name.as_bytes());
+        buf.extend(&entry.data);
+
+        central.extend(0x02014b50u32.to_le_bytes());
+        central.extend(20u16.to_le_bytes());
+        fields(&mut central);
+        central.extend([0u8; 6]);
+        central.extend(0u32.to_le_bytes());
+        central.extend(offset.to_le_bytes());
+        central.extend(entry.name.as_bytes());
+    }
+
+    let (central_offset, central_len) = (u32_len(buf.len())?, u32_len(central.len())?);
+    buf.extend(&central);
+
+    buf.extend(0x06054b50u32.to_le_bytes());
+    buf.extend([0u8; 4]);
+    buf.extend(count.to_le_bytes());
+    buf.extend(count.to_le_bytes());
+    buf.extend(central_len.to_le_bytes());
+    buf.extend(central_offset.to_le_bytes());
+    buf.extend(0u16.to_le_bytes());
+
+    Ok(buf)
+}
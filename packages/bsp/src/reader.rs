@@ -0,0 +1,64 @@
+use bincode::error::DecodeError;
+
+pub(crate) struct Reader<'a> {
+    pub buf: &'a [u8],
+    pub pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Reader<'a> {
+        Reader { buf, pos: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len().saturating_sub(self.pos)
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let bytes = self.buf.get(self.pos..self.pos + len).ok_or_else(|| DecodeError::UnexpectedEnd {
+            additional: len - self.remaining(),
+        })?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub fn i32(&mut self) -> Result<i32, DecodeError> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    pub fn f32(&mut self) -> Result<f32, DecodeError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    pub fn vector2(&mut self) -> Result<[f32; 2], DecodeError> {
+        Ok([self.f32()?, self.f32()?])
+    }
+
+    pub fn vector(&mut self) -> Result<[f32; 3], DecodeError> {
+        Ok([self.f32()?, self.f32()?, self.f32()?])
+    }
+
+    pub fn string(&mut self, len: usize) -> Result<String, DecodeError> {
+        let bytes = self.take(len)?;
+        let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(len);
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+}
@@ -0,0 +1,111 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    bsp::{BSP, BSPError, HEADER_SIZE, Lump, compress},
+    lump::LumpKind,
+};
+
+const GAME_LUMP_ENTRY_SIZE: usize = 16;
+
+fn rebase_game_lump(buf: &mut [u8], delta: i64) {
+    let count = buf
+        .get(0..4)
+        .map_or(0, |count| i32::from_le_bytes([count[0], count[1], count[2], count[3]]).max(0) as usize);
+
+    for i in 0..count {
+        let start = 4 + i * GAME_LUMP_ENTRY_SIZE + 8;
+        let Some(offset) = buf.get_mut(start..start + 4) else {
+            break;
+        };
+
+        let value = i32::from_le_bytes([offset[0], offset[1], offset[2], offset[3]]);
+        if value != 0 {
+            offset.copy_from_slice(&((value as i64 + delta) as i32).to_le_bytes());
+        }
+    }
+}
+
+impl BSP {
+    fn replace_lump(&mut self, i: usize, data: Vec<u8>, four_cc: [u8; 4]) -> Result<(), BSPError> {
+        let game_lump = LumpKind::GameLump as usize;
+        let pakfile = LumpKind::Pakfile as usize;
+
+        // Keep the existing layout, except the pakfile which the engine expects to be last.
+        let mut order = (0..self.header.lumps.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|j| (*j == pakfile, self.header.lumps[*j].offset));
+
+        let mut header = self.header;
+        let mut buf = vec![0; HEADER_SIZE];
+
+        for j in order {
+            let old = self.header.lumps[j];
+            let bytes = match j == i {
+                true => &data,
+                false => self.raw_lump(j)?,
+            };
+
+            buf.resize(buf.len().next_multiple_of(4), 0);
+            let offset = buf.len();
+
+            header.lumps[j] = Lump {
+                offset: if bytes.is_empty() { 0 } else { offset as i32 },
+                len: bytes.len() as i32,
+                version: old.version,
+                four_cc: if j == i { four_cc } else { old.four_cc },
+            };
+            buf.extend(bytes);
+
+            // Game lump entries hold absolute file offsets, so they move with the lump.
+            if j == game_lump {
+                rebase_game_lump(&mut buf[offset..], offset as i64 - old.offset as i64);
+            }
+        }
+
+        let config = bincode::config::standard().with_fixed_int_encoding();
+        bincode::encode_into_slice(header, &mut buf[..HEADER_SIZE], config)?;
+
+        self.buf = buf;
+        self.header = header;
+
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl BSP {
+    /// Offsets inside a replacement game lump are absolute, as returned by `lump_of`, and are rebased from where the old lump was.
+    pub fn set_lump(&mut self, kind: LumpKind, data: Vec<u8>, lzma: bool) -> Result<(), BSPError> {
+        let i = kind as usize;
+
+        if !lzma || data.is_empty() {
+            return self.replace_lump(i, data, [0; 4]);
+        }
+
+        // Game lumps compress their sub-lumps individually, and the pakfile is a zip.
+        if matches!(kind, LumpKind::GameLump | LumpKind::Pakfile) {
+            return Err(BSPError::UnexpectedCompression { found: i });
+        }
+
+        let (properties, stream) = compress(&data)?;
+
+        let mut buf = Vec::with_capacity(17 + stream.len());
+        buf.extend(b"LZMA");
+        buf.extend((data.len() as u32).to_le_bytes());
+        buf.extend((stream.len() as u32).to_le_bytes());
+        buf.extend(properties);
+        buf.extend(stream);
+
+        self.replace_lump(i, buf, (data.len() as u32).to_le_bytes())
+    }
+
+    pub fn save(&self) -> Result<Vec<u8>, BSPError> {
+        let mut header = self.header;
+        header.map_revision += 1;
+
+        let mut buf = self.buf.clone();
+        let config = bincode::config::standard().with_fixed_int_encoding();
+        bincode::encode_into_slice(header, &mut buf[..HEADER_SIZE], config)?;
+
+        Ok(buf)
+    }
+}
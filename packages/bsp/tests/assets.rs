@@ -0,0 +1,35 @@
+use bsp::BSP;
+
+const HEADER_SIZE: usize = 1036;
+const ENTITIES: &[u8] = b"{\n\"classname\" \"worldspawn\"\n}\n{\n\"classname\" \"ambient_generic\"\n\"message\" \"*#music/song.mp3\"\n}\n{\n\"classname\" \"func_brush\"\n\"model\" \"*3\"\n}\n\0";
+
+fn bsp() -> BSP {
+    let mut buf = b"VBSP".to_vec();
+    buf.extend(20i32.to_le_bytes());
+    for i in 0..64 {
+        let (offset, len) = match i {
+            0 => (HEADER_SIZE, ENTITIES.len()),
+            _ => (0, 0),
+        };
+        for value in [offset as i32, len as i32, 0] {
+            buf.extend(value.to_le_bytes());
+        }
+        buf.extend([0u8; 4]);
+    }
+    buf.extend(1i32.to_le_bytes());
+    buf.extend(ENTITIES);
+
+    BSP::new(buf).unwrap()
+}
+
+#[test]
+fn only_brush_model_references_are_skipped() {
+    let assets = bsp().assets().unwrap();
+    let paths = assets
+        .0
+        .iter()
+        .map(|asset| (asset.path.as_str(), asset.references.join(",")))
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [("sound/music/song.mp3", "ambient_generic.message".to_string())]);
+}
@@ -0,0 +1,48 @@
+use bsp::{Entities, EntityLump};
+
+const LUMP: &[u8] = b"{\n\"classname\" \"worldspawn\"\n\"message\" \"caf\xe9\"\n}\r\n{\n\"classname\" \"info_player_start\"\n}\n\0";
+
+#[test]
+fn unedited_lump_serialises_to_the_same_bytes() {
+    let lump = EntityLump::new(LUMP).unwrap();
+    assert_eq!(lump.serialise(), LUMP);
+}
+
+#[test]
+fn edits_keep_the_bytes_of_other_entities() {
+    let mut lump = EntityLump::new(LUMP).unwrap();
+    lump.set(1, "origin", "0 0 64").unwrap();
+
+    let serialised = lump.serialise();
+    assert!(serialised.starts_with(b"{\n\"classname\" \"worldspawn\"\n\"message\" \"caf\xe9\"\n}\r\n"));
+    assert!(serialised.ends_with(b"\"origin\" \"0 0 64\"\n}\n\0"));
+}
+
+#[test]
+fn edits_keep_the_raw_bytes_of_the_edited_entity() {
+    let mut lump = EntityLump::new(LUMP).unwrap();
+    assert_eq!(lump.get(0, "message").as_deref(), Some("caf\u{FFFD}"));
+
+    lump.set(0, "skyname", "sky_day01_01").unwrap();
+    lump.remove_key(0, "classname").unwrap();
+    lump.append(0, "classname", "worldspawn").unwrap();
+
+    let serialised = lump.serialise();
+    assert!(serialised.starts_with(b"{\n\"message\" \"caf\xe9\"\n\"skyname\" \"sky_day01_01\"\n\"classname\" \"worldspawn\"\n}\r\n"));
+}
+
+#[test]
+fn entities_keep_order_and_raw_offsets() {
+    let entities = Entities::new(LUMP).unwrap();
+    let classnames = entities.iter().map(|entity| entity.get("classname").unwrap()).collect::<Vec<_>>();
+    assert_eq!(classnames, ["worldspawn", "info_player_start"]);
+
+    let classname = entities.0[1].keyvalues[0].key_span.unwrap();
+    assert_eq!((classname.start.offset, classname.start.line, classname.start.column), (49, 6, 1));
+}
+
+#[test]
+fn syntax_errors_index_the_raw_lump() {
+    let err = Entities::new(b"{\n\"message\" \"caf\xe9\"\n\"x\"\n}").unwrap_err();
+    assert_eq!((err.location.offset, err.location.line, err.location.column), (23, 4, 1));
+}
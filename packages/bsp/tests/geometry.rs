@@ -0,0 +1,161 @@
+use bsp::BSP;
+use serde_json::Value;
+
+const HEADER_SIZE: usize = 1036;
+
+const PLANES: usize = 1;
+const TEXDATA: usize = 2;
+const VERTEXES: usize = 3;
+const TEXINFO: usize = 6;
+const FACES: usize = 7;
+const EDGES: usize = 12;
+const SURFEDGES: usize = 13;
+const MODELS: usize = 14;
+const DISPINFO: usize = 26;
+const TEXDATA_STRING_DATA: usize = 43;
+const TEXDATA_STRING_TABLE: usize = 44;
+
+fn bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+fn face(dispinfo: i16) -> Vec<u8> {
+    let mut buf = vec![];
+    buf.extend(0u16.to_le_bytes());
+    buf.extend([0u8, 0]);
+    buf.extend(0i32.to_le_bytes());
+    buf.extend(4i16.to_le_bytes());
+    buf.extend(0i16.to_le_bytes());
+    buf.extend(dispinfo.to_le_bytes());
+    buf.resize(56, 0);
+    buf
+}
+
+fn disp_info(first_vertex: i32) -> Vec<u8> {
+    let mut buf = bytes(&[0.0, 0.0, 0.0]);
+    buf.extend(first_vertex.to_le_bytes());
+    buf.extend(0i32.to_le_bytes());
+    buf.extend(2i32.to_le_bytes());
+    buf.resize(176, 0);
+    buf
+}
+
+// A single 64x64 quad on the z = 0 plane, in model 0 with the material `dev/floor`.
+fn bsp(face: Vec<u8>, extra: Vec<(usize, Vec<u8>)>) -> BSP {
+    let mut texinfo = bytes(&[1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    texinfo.resize(72, 0);
+
+    let mut texdata = bytes(&[0.0, 0.0, 0.0]);
+    for value in [0i32, 64, 64, 64, 64] {
+        texdata.extend(value.to_le_bytes());
+    }
+
+    let mut model = bytes(&[0.0; 9]);
+    for value in [0i32, 0, 1] {
+        model.extend(value.to_le_bytes());
+    }
+
+    let mut lumps = vec![
+        (PLANES, [bytes(&[0.0, 0.0, 1.0, 0.0]), 0i32.to_le_bytes().to_vec()].concat()),
+        (TEXDATA, texdata),
+        (VERTEXES, bytes(&[0.0, 0.0, 0.0, 64.0, 0.0, 0.0, 64.0, 64.0, 0.0, 0.0, 64.0, 0.0])),
+        (TEXINFO, texinfo),
+        (FACES, face),
+        (EDGES, [0u16, 0, 0, 1, 1, 2, 2, 3, 3, 0].iter().flat_map(|value| value.to_le_bytes()).collect()),
+        (SURFEDGES, [1i32, 2, 3, 4].iter().flat_map(|value| value.to_le_bytes()).collect()),
+        (MODELS, model),
+        (TEXDATA_STRING_DATA, b"dev/floor\0".to_vec()),
+        (TEXDATA_STRING_TABLE, 0i32.to_le_bytes().to_vec()),
+    ];
+    lumps.extend(extra);
+
+    let mut buf = vec![0; HEADER_SIZE];
+    let mut header = b"VBSP".to_vec();
+    header.extend(20i32.to_le_bytes());
+    for i in 0..64 {
+        let (offset, len) = match lumps.iter().find(|(index, _)| *index == i) {
+            Some((_, data)) => {
+                buf.resize(buf.len().next_multiple_of(4), 0);
+                let offset = buf.len();
+                buf.extend(data);
+                (offset, data.len())
+            }
+            None => (0, 0),
+        };
+        for value in [offset as i32, len as i32, 0] {
+            header.extend(value.to_le_bytes());
+        }
+        header.extend([0u8; 4]);
+    }
+    header.extend(1i32.to_le_bytes());
+    buf[..HEADER_SIZE].copy_from_slice(&header);
+
+    BSP::new(buf).unwrap()
+}
+
+#[test]
+fn meshes_fan_triangulate_faces() {
+    let meshes = bsp(face(-1), vec![]).meshes().unwrap();
+    assert_eq!(meshes.len(), 1);
+
+    let mesh = &meshes[0];
+    assert_eq!(mesh.positions, [[0.0, 0.0, 0.0], [64.0, 0.0, 0.0], [64.0, 64.0, 0.0], [0.0, 64.0, 0.0]]);
+    assert_eq!(mesh.normals, [[0.0, 0.0, 1.0]; 4]);
+    assert_eq!(mesh.uvs, [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+
+    assert_eq!(mesh.groups.len(), 1);
+    assert_eq!(mesh.groups[0].material, "dev/floor");
+    assert_eq!(mesh.groups[0].indices, [0, 1, 2, 0, 2, 3]);
+}
+
+#[test]
+fn obj_reverses_the_winding() {
+    let obj = bsp(face(-1), vec![]).obj().unwrap();
+    let faces = obj.lines().filter(|line| line.starts_with("f ")).collect::<Vec<_>>();
+
+    assert!(obj.contains("usemtl dev/floor\n"));
+    assert_eq!(faces, ["f 1/1/1 3/3/3 2/2/2", "f 1/1/1 4/4/4 3/3/3"]);
+}
+
+#[test]
+fn glb_accessors_match_the_mesh() {
+    let glb = bsp(face(-1), vec![]).glb().unwrap();
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+
+    let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+    assert_eq!(&glb[16..20], b"JSON");
+    let gltf: Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+
+    let counts = gltf["accessors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|accessor| accessor["count"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(counts, [4, 4, 4, 6]);
+    assert_eq!(gltf["accessors"][0]["min"], serde_json::json!([0.0, 0.0, -64.0]));
+    assert_eq!(gltf["accessors"][0]["max"], serde_json::json!([64.0, 0.0, 0.0]));
+    assert_eq!(gltf["meshes"][0]["primitives"][0]["indices"], 3);
+    assert_eq!(gltf["materials"][0]["name"], "dev/floor");
+
+    let bin = &glb[20 + json_len..];
+    assert_eq!(&bin[4..8], b"BIN\0");
+    let view = &gltf["bufferViews"][3];
+    let offset = 8 + view["byteOffset"].as_u64().unwrap() as usize;
+    let indices = bin[offset..offset + 24]
+        .chunks_exact(4)
+        .map(|index| u32::from_le_bytes(index.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(indices, [0, 2, 1, 0, 3, 2]);
+}
+
+#[test]
+fn displacements_with_a_negative_first_vertex_stay_flat() {
+    let meshes = bsp(face(0), vec![(DISPINFO, disp_info(-1))]).meshes().unwrap();
+
+    let mesh = &meshes[0];
+    assert_eq!(mesh.positions.len(), 25);
+    assert!(mesh.positions.iter().all(|position| position[2] == 0.0));
+    assert_eq!(mesh.groups[0].indices.len(), 4 * 4 * 6);
+}
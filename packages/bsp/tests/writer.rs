@@ -0,0 +1,120 @@
+use bsp::{BSP, LumpKind};
+
+const HEADER_SIZE: usize = 1036;
+const ENTITIES: &[u8] = b"{\n\"classname\" \"worldspawn\"\n}\n\0";
+
+fn static_props() -> Vec<u8> {
+    let mut buf = vec![];
+
+    buf.extend(1i32.to_le_bytes());
+    let mut name = b"models/props/crate.mdl".to_vec();
+    name.resize(128, 0);
+    buf.extend(name);
+
+    buf.extend(1i32.to_le_bytes());
+    buf.extend(7u16.to_le_bytes());
+
+    buf.extend(1i32.to_le_bytes());
+    let mut prop = vec![];
+    for value in [1.0f32, 2.0, 3.0, 0.0, 90.0, 0.0] {
+        prop.extend(value.to_le_bytes());
+    }
+    prop.extend(0u16.to_le_bytes());
+    prop.extend(0u16.to_le_bytes());
+    prop.extend(1u16.to_le_bytes());
+    prop.extend([6u8, 1]);
+    prop.extend(2i32.to_le_bytes());
+    for value in [100.0f32, 200.0, 1.0, 2.0, 3.0, 1.0] {
+        prop.extend(value.to_le_bytes());
+    }
+    prop.resize(72, 0);
+    buf.extend(prop);
+
+    buf
+}
+
+// A game lump with one sprp entry, its offset absolute for a lump starting at `base`.
+fn game_lump(base: usize) -> Vec<u8> {
+    let data = static_props();
+    let offset = base + 4 + 16;
+
+    let mut buf = vec![];
+    buf.extend(1i32.to_le_bytes());
+    buf.extend(u32::from_be_bytes(*b"sprp").to_le_bytes());
+    buf.extend(0u16.to_le_bytes());
+    buf.extend(10u16.to_le_bytes());
+    buf.extend((offset as i32).to_le_bytes());
+    buf.extend((data.len() as i32).to_le_bytes());
+    buf.extend(data);
+
+    buf
+}
+
+fn bsp() -> BSP {
+    let game_lump_offset = HEADER_SIZE + ENTITIES.len().next_multiple_of(4);
+    let game_lump = game_lump(game_lump_offset);
+
+    let mut buf = b"VBSP".to_vec();
+    buf.extend(20i32.to_le_bytes());
+    for i in 0..64 {
+        let (offset, len) = match i {
+            0 => (HEADER_SIZE, ENTITIES.len()),
+            35 => (game_lump_offset, game_lump.len()),
+            _ => (0, 0),
+        };
+        for value in [offset as i32, len as i32, 0] {
+            buf.extend(value.to_le_bytes());
+        }
+        buf.extend([0u8; 4]);
+    }
+    buf.extend(1i32.to_le_bytes());
+
+    buf.extend(ENTITIES);
+    buf.resize(game_lump_offset, 0);
+    buf.extend(game_lump);
+
+    BSP::new(buf).unwrap()
+}
+
+fn game_lumps(bsp: &BSP) -> Vec<(String, u16, u16, i32, Vec<u8>)> {
+    bsp.game_lumps()
+        .unwrap()
+        .0
+        .into_iter()
+        .map(|game_lump| {
+            let (_, data) = bsp.game_lump(&game_lump.id).unwrap().unwrap();
+            (game_lump.id, game_lump.flags, game_lump.version, game_lump.len, data)
+        })
+        .collect()
+}
+
+fn props(bsp: &BSP) -> String {
+    format!("{:?}", bsp.static_props().unwrap())
+}
+
+#[test]
+fn moving_the_game_lump_keeps_its_contents() {
+    let mut bsp = bsp();
+    let (before, before_props) = (game_lumps(&bsp), props(&bsp));
+
+    bsp.set_lump(LumpKind::Entities, ENTITIES.repeat(8), false).unwrap();
+    let bsp = BSP::new(bsp.save().unwrap()).unwrap();
+
+    assert_eq!(bsp.lump_of(LumpKind::Entities).unwrap(), ENTITIES.repeat(8));
+    assert_eq!(game_lumps(&bsp), before);
+    assert_eq!(props(&bsp), before_props);
+}
+
+#[test]
+fn game_lump_round_trips_through_set_lump() {
+    let mut bsp = bsp();
+    let (before, before_props) = (game_lumps(&bsp), props(&bsp));
+
+    bsp.set_lump(LumpKind::Entities, ENTITIES.repeat(8), false).unwrap();
+    let game_lump = bsp.lump_of(LumpKind::GameLump).unwrap();
+    bsp.set_lump(LumpKind::GameLump, game_lump, false).unwrap();
+    let bsp = BSP::new(bsp.save().unwrap()).unwrap();
+
+    assert_eq!(game_lumps(&bsp), before);
+    assert_eq!(props(&bsp), before_props);
+}
@@ -260,7 +260,16 @@ export function TRPCClientRouter(
 									classname: z.string(),
 									targetname: z.string().optional(),
 								}).catchall(z.union([z.string(), z.array(z.string())]))
-							).parse(bsp.entities())
+							).parse(
+								bsp.entities().filter(({ keyvalues }) => keyvalues.length > 0).map(({ keyvalues }) => {
+									const entity: Record<string, string | string[]> = {}
+									for (const { key, value } of keyvalues) {
+										const existing = entity[key]
+										entity[key] = existing == undefined ? value : [existing, value].flat()
+									}
+									return entity
+								})
+							)
 						}
 						catch (error) {
 							console.error(error)
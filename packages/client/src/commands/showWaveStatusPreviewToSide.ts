@@ -804,7 +804,7 @@ export function showWaveStatusPreviewToSide(
 
 										await using bsp = await bspFactory.get(entry.uri)
 										const entities = bsp.entities()
-										const skyname = entities[0]["skyname"]
+										const skyname = entities[0]?.keyvalues.find(({ key }) => key == "skyname")?.value
 
 										// bk
 										// ft
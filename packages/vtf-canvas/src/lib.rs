@@ -1,4 +1,4 @@
-use vtf::{VTF, VTFData, VTFExtractError};
+use vtf::{VTF, VTFData, VTFExtractError, flags};
 use wasm_bindgen::prelude::wasm_bindgen;
 use web_sys::{CanvasRenderingContext2d, ImageData, wasm_bindgen::Clamped};
 
@@ -9,3 +9,29 @@ pub fn vtf_put_image_data(vtf: &VTF, context: &CanvasRenderingContext2d, mipmap_
     context.put_image_data(&data, 0.0, 0.0).unwrap();
     Ok(())
 }
+
+#[wasm_bindgen(js_name = "VTFPutContactSheet")]
+pub fn vtf_put_contact_sheet(vtf: &VTF, context: &CanvasRenderingContext2d, frames: bool) -> Result<(), VTFExtractError> {
+    let VTFData { width, height, rgba } = vtf.contact_sheet(frames)?;
+    if let Some(canvas) = context.canvas() {
+        canvas.set_width(width as u32);
+        canvas.set_height(height as u32);
+    }
+
+    let data = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&rgba), width as u32, height as u32).unwrap();
+    context.put_image_data(&data, 0.0, 0.0).unwrap();
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "VTFPutSSBumpNormalData")]
+pub fn vtf_put_ssbump_normal_data(vtf: &VTF, context: &CanvasRenderingContext2d, mipmap_index: usize, frame_index: usize) -> Result<(), VTFExtractError> {
+    let data = vtf.extract(mipmap_index, frame_index)?;
+    let VTFData { width, height, rgba } = match vtf.header.flags & flags::SSBUMP {
+        0 => data,
+        _ => data.ssbump_to_normal(),
+    };
+
+    let data = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&rgba), width as u32, height as u32).unwrap();
+    context.put_image_data(&data, 0.0, 0.0).unwrap();
+    Ok(())
+}
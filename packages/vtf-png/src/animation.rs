@@ -0,0 +1,69 @@
+use std::io::Cursor;
+
+use image::{AnimationDecoder, ImageError, ImageFormat, codecs::gif::GifDecoder, codecs::png::PngDecoder};
+use js_sys::Uint8Array;
+use thiserror::Error;
+use vtf::{VTF, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat};
+use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub enum VTFImageError {
+    ImageError(#[from] ImageError),
+    EncodeError(#[from] VTFEncodeError),
+}
+
+impl From<VTFImageError> for JsValue {
+    fn from(value: VTFImageError) -> Self {
+        JsValue::from(JsError::new(&format!("{:?}", value)))
+    }
+}
+
+fn to_vtf_data(image: image::RgbaImage) -> Result<VTFData, VTFImageError> {
+    Ok(VTFData::new(image.width() as u16, image.height() as u16, image.into_raw())?)
+}
+
+pub fn decode_image(buf: &[u8]) -> Result<VTFData, VTFImageError> {
+    to_vtf_data(image::load_from_memory(buf)?.to_rgba8())
+}
+
+pub fn decode_animation(buf: &[u8]) -> Result<Vec<VTFData>, VTFImageError> {
+    let frames = match image::guess_format(buf)? {
+        ImageFormat::Gif => GifDecoder::new(Cursor::new(buf))?.into_frames().collect_frames()?,
+        ImageFormat::Png if PngDecoder::new(Cursor::new(buf))?.is_apng()? => PngDecoder::new(Cursor::new(buf))?.apng()?.into_frames().collect_frames()?,
+        _ => return Ok(vec![decode_image(buf)?]),
+    };
+
+    frames.into_iter().map(|frame| to_vtf_data(frame.into_buffer())).collect()
+}
+
+#[wasm_bindgen(js_name = "VTFFromImages")]
+pub fn vtf_from_images(images: Vec<Uint8Array>, format: VTFImageFormat, first_frame: u16, mipmaps: bool) -> Result<Vec<u8>, VTFImageError> {
+    let frames = images
+        .iter()
+        .map(|image| decode_image(&image.to_vec()))
+        .collect::<Result<Vec<VTFData>, VTFImageError>>()?;
+
+    Ok(VTF::encode(
+        frames,
+        &VTFEncodeOptions {
+            format,
+            first_frame,
+            mipmaps,
+            ..Default::default()
+        },
+    )?)
+}
+
+#[wasm_bindgen(js_name = "VTFFromAnimation")]
+pub fn vtf_from_animation(buf: Vec<u8>, format: VTFImageFormat, first_frame: u16, mipmaps: bool) -> Result<Vec<u8>, VTFImageError> {
+    Ok(VTF::encode(
+        decode_animation(&buf)?,
+        &VTFEncodeOptions {
+            format,
+            first_frame,
+            mipmaps,
+            ..Default::default()
+        },
+    )?)
+}
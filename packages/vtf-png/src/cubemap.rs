@@ -0,0 +1,58 @@
+use image::DynamicImage;
+use js_sys::Uint8Array;
+use vtf::{VTF, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{VTFImageError, decode_image};
+
+pub fn decode_panorama(buf: &[u8]) -> Result<VTFData, VTFImageError> {
+    let image = image::load_from_memory(buf)?;
+
+    let rgba = match image {
+        DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+            let mut hdr = image.to_rgba32f();
+            for pixel in hdr.pixels_mut() {
+                for value in &mut pixel.0[..3] {
+                    *value = (*value / (1.0 + *value)).powf(1.0 / 2.2);
+                }
+            }
+            DynamicImage::ImageRgba32F(hdr).to_rgba8()
+        }
+        _ => image.to_rgba8(),
+    };
+
+    Ok(VTFData::new(rgba.width() as u16, rgba.height() as u16, rgba.into_raw())?)
+}
+
+#[wasm_bindgen(js_name = "VTFCubemapFromImages")]
+pub fn vtf_cubemap_from_images(faces: Vec<Uint8Array>, spheremap: bool, format: VTFImageFormat, version_minor: u32) -> Result<Vec<u8>, VTFImageError> {
+    let faces = faces
+        .iter()
+        .map(|face| decode_image(&face.to_vec()))
+        .collect::<Result<Vec<VTFData>, VTFImageError>>()?;
+    let found = faces.len();
+    let faces: [VTFData; 6] = faces.try_into().map_err(|_| VTFEncodeError::UnexpectedImageCount { faces: 6, found })?;
+
+    Ok(VTF::cubemap(
+        faces,
+        spheremap,
+        &VTFEncodeOptions {
+            format,
+            version_minor,
+            ..Default::default()
+        },
+    )?)
+}
+
+#[wasm_bindgen(js_name = "VTFCubemapFromPanorama")]
+pub fn vtf_cubemap_from_panorama(buf: Vec<u8>, size: u16, spheremap: bool, format: VTFImageFormat, version_minor: u32) -> Result<Vec<u8>, VTFImageError> {
+    Ok(VTF::cubemap(
+        decode_panorama(&buf)?.equirectangular_to_cubemap(size),
+        spheremap,
+        &VTFEncodeOptions {
+            format,
+            version_minor,
+            ..Default::default()
+        },
+    )?)
+}
@@ -2,11 +2,16 @@ use std::{cmp, io::Cursor};
 
 use base64::{Engine, engine::general_purpose};
 use image::{ColorType, DynamicImage, ImageFormat, RgbaImage};
-use vtf::{VTF, VTFData, VTFExtractError};
+use vtf::{VTF, VTFData, VTFExtractError, flags};
 use wasm_bindgen::prelude::wasm_bindgen;
 
-#[wasm_bindgen(js_name = "VTFToPNG")]
-pub fn vtf_to_png(vtf: &VTF, size: u16) -> Result<Vec<u8>, VTFExtractError> {
+mod animation;
+mod cubemap;
+
+pub use animation::{VTFImageError, decode_animation, decode_image, vtf_from_animation, vtf_from_images};
+pub use cubemap::{decode_panorama, vtf_cubemap_from_images, vtf_cubemap_from_panorama};
+
+fn extract_thumbnail(vtf: &VTF, size: u16) -> Result<VTFData, VTFExtractError> {
     let mipmap_index = match vtf.header.mipmap_count {
         1 => 0,
         _ => vtf
@@ -20,7 +25,11 @@ pub fn vtf_to_png(vtf: &VTF, size: u16) -> Result<Vec<u8>, VTFExtractError> {
             .unwrap_or(0),
     };
 
-    let VTFData { width, height, rgba } = vtf.extract(mipmap_index, 0)?;
+    vtf.extract(mipmap_index, 0)
+}
+
+fn encode_png(data: VTFData, size: u16) -> Vec<u8> {
+    let VTFData { width, height, rgba } = data;
     let mut out = vec![];
 
     if cmp::max(width, height) > size {
@@ -39,10 +48,44 @@ pub fn vtf_to_png(vtf: &VTF, size: u16) -> Result<Vec<u8>, VTFExtractError> {
         .unwrap();
     };
 
-    Ok(out)
+    out
+}
+
+#[wasm_bindgen(js_name = "VTFToPNG")]
+pub fn vtf_to_png(vtf: &VTF, size: u16) -> Result<Vec<u8>, VTFExtractError> {
+    Ok(encode_png(extract_thumbnail(vtf, size)?, size))
 }
 
 #[wasm_bindgen(js_name = "VTFToPNGBase64")]
 pub fn vtf_to_png_base64(vtf: &VTF, size: u16) -> Result<String, VTFExtractError> {
     vtf_to_png(vtf, size).map(|out| format!("![](data:image/png;base64,{})", general_purpose::STANDARD.encode(out)))
 }
+
+#[wasm_bindgen(js_name = "VTFContactSheetToPNG")]
+pub fn vtf_contact_sheet_to_png(vtf: &VTF, frames: bool) -> Result<Vec<u8>, VTFExtractError> {
+    Ok(encode_png(vtf.contact_sheet(frames)?, u16::MAX))
+}
+
+#[wasm_bindgen(js_name = "VTFSSBumpNormalToPNG")]
+pub fn vtf_ssbump_normal_to_png(vtf: &VTF, size: u16) -> Result<Vec<u8>, VTFExtractError> {
+    let data = extract_thumbnail(vtf, size)?;
+
+    match vtf.header.flags & flags::SSBUMP {
+        0 => Ok(encode_png(data, size)),
+        _ => Ok(encode_png(data.ssbump_to_normal(), size)),
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFDiffToPNG")]
+pub fn vtf_diff_to_png(before: &VTF, after: &VTF, frame_index: usize) -> Result<Vec<u8>, VTFExtractError> {
+    match before.matching_mipmaps(after)?.last() {
+        Some((index, other_index, _)) => {
+            let heatmap = before.extract(*index, frame_index)?.heatmap(&after.extract(*other_index, frame_index)?);
+            Ok(encode_png(heatmap, u16::MAX))
+        }
+        None => Err(VTFExtractError::UnexpectedMipMap {
+            mipmap_count: after.header.mipmap_count,
+            found: 0,
+        }),
+    }
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{flags, VTFExtractError, VTFHeader, VTFImageFormat, VTF};
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct VTFBudget {
+    pub file_bytes: usize,
+    pub header_bytes: usize,
+    pub thumbnail_bytes: usize,
+    pub image_bytes: usize,
+    pub vram_bytes: usize,
+    pub frames: usize,
+    pub faces: usize,
+    pub mipmaps: Vec<VTFMipMapBudget>,
+    pub formats: Vec<VTFFormatBudget>,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFMipMapBudget {
+    pub width: u16,
+    pub height: u16,
+    pub face_bytes: usize,
+    pub frame_bytes: usize,
+    pub bytes: usize,
+    pub vram_bytes: usize,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFFormatBudget {
+    pub format: String,
+    pub image_bytes: usize,
+    pub vram_bytes: usize,
+}
+
+impl VTFImageFormat {
+    pub fn vram_bytes(&self, width: usize, height: usize) -> Result<usize, VTFImageFormat> {
+        match self {
+            VTFImageFormat::RGB888 | VTFImageFormat::BGR888 | VTFImageFormat::RGB888BlueScreen | VTFImageFormat::BGR888BlueScreen => Ok(width * height * 4),
+            format => format.bytes(width, height),
+        }
+    }
+}
+
+impl VTFHeader {
+    fn mipmap_budgets(&self, format: VTFImageFormat) -> Result<Vec<VTFMipMapBudget>, VTFImageFormat> {
+        let frames = self.frames as usize;
+        let faces = self.faces();
+
+        (0..self.mipmap_count)
+            .rev()
+            .map(|i| {
+                let width = (self.width as usize >> i).max(1);
+                let height = (self.height as usize >> i).max(1);
+
+                let face_bytes = format.bytes(width, height)?;
+                let resident = i == 0 || self.flags & flags::NOMIP == 0;
+
+                Ok(VTFMipMapBudget {
+                    width: width as u16,
+                    height: height as u16,
+                    face_bytes,
+                    frame_bytes: face_bytes * faces,
+                    bytes: face_bytes * faces * frames,
+                    vram_bytes: if resident { format.vram_bytes(width, height)? * faces * frames } else { 0 },
+                })
+            })
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn budget(&self) -> Result<VTFBudget, VTFExtractError> {
+        let header = &self.header;
+        let mipmaps = header.mipmap_budgets(header.high_res_image_format)?;
+
+        let formats = VTFImageFormat::FORMATS
+            .iter()
+            .filter_map(|format| header.mipmap_budgets(*format).ok().map(|mipmaps| (format, mipmaps)))
+            .map(|(format, mipmaps)| VTFFormatBudget {
+                format: format!("{:?}", format),
+                image_bytes: mipmaps.iter().map(|mipmap| mipmap.bytes).sum(),
+                vram_bytes: mipmaps.iter().map(|mipmap| mipmap.vram_bytes).sum(),
+            })
+            .collect();
+
+        Ok(VTFBudget {
+            file_bytes: self.buf.len(),
+            header_bytes: header.header_size as usize,
+            thumbnail_bytes: header.thumbnail_bytes(),
+            image_bytes: mipmaps.iter().map(|mipmap| mipmap.bytes).sum(),
+            vram_bytes: mipmaps.iter().map(|mipmap| mipmap.vram_bytes).sum(),
+            frames: header.frames as usize,
+            faces: header.faces(),
+            mipmaps,
+            formats,
+        })
+    }
+}
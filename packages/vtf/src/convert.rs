@@ -0,0 +1,183 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    encode::{IMAGE_TAG, NO_DATA, THUMBNAIL_TAG},
+    flags, VTFEncodeError, VTFExtractError, VTFHeader, VTFResourceEntryInfo, VTF,
+};
+
+#[derive(Debug, Clone)]
+pub struct VTFConversion {
+    pub buf: Vec<u8>,
+    pub lost: Vec<VTFResourceLoss>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VTFResourceLoss {
+    pub name: String,
+    pub bytes: usize,
+}
+
+impl VTFResourceEntryInfo {
+    pub fn name(&self) -> String {
+        match self._tag {
+            THUMBNAIL_TAG => "Thumbnail".to_string(),
+            IMAGE_TAG => "Image".to_string(),
+            [0x10, 0, 0] => "Sheet".to_string(),
+            tag if tag.iter().all(|c| c.is_ascii_alphanumeric()) => String::from_utf8_lossy(&tag).into_owned(),
+            tag => format!("{:02X}{:02X}{:02X}", tag[0], tag[1], tag[2]),
+        }
+    }
+}
+
+impl VTF {
+    fn resource_data(&self, resource: &VTFResourceEntryInfo) -> Result<&[u8], VTFExtractError> {
+        if resource.flags & NO_DATA != 0 {
+            return Ok(&[]);
+        }
+
+        let offset = resource.offset as usize;
+        let length = self
+            .buf
+            .get(offset..offset + 4)
+            .map(|length| u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize)
+            .ok_or(VTFExtractError::UnexpectedEnd { additional: 4 })?;
+
+        self.buf
+            .get(offset..offset + 4 + length)
+            .ok_or(VTFExtractError::UnexpectedEnd { additional: length })
+    }
+
+    pub fn convert_version(&self, version_minor: u32) -> Result<VTFConversion, VTFEncodeError> {
+        if !(0..=5).contains(&version_minor) {
+            return Err(VTFEncodeError::UnexpectedVersion { version_minor });
+        }
+
+        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
+        let source_faces = self.header.faces();
+
+        let mut header = VTFHeader { version_minor, ..self.header };
+        if self.header.flags & flags::ENVMAP != 0 && source_faces == 6 && version_minor < 5 {
+            header.first_frame = 0xFFFF;
+        }
+        let faces = header.faces();
+
+        let mut lost = Vec::new();
+
+        let image = mipmaps
+            .iter()
+            .enumerate()
+            .flat_map(|(mipmap_index, mipmap)| (0..mipmap.frames.len()).map(move |frame_index| (mipmap_index, frame_index)))
+            .flat_map(|(mipmap_index, frame_index)| (0..source_faces).map(move |face_index| (mipmap_index, frame_index, face_index)))
+            .map(|(mipmap_index, frame_index, face_index)| self.face(mipmap_index, frame_index, face_index).map(|(_, buf)| (face_index, buf)))
+            .try_fold(Vec::new(), |mut image, face| -> Result<Vec<u8>, VTFExtractError> {
+                let (face_index, buf) = face?;
+                if face_index < faces {
+                    image.extend(buf);
+                }
+                Ok(image)
+            })?;
+
+        if faces < source_faces {
+            lost.push(VTFResourceLoss {
+                name: "Spheremap".to_string(),
+                bytes: self.header.image_bytes(self.header.high_res_image_format, self.header.mipmap_count)? / source_faces,
+            });
+        }
+
+        let header_size = self.header.header_size as usize;
+        let thumbnail_offset = self
+            .resources
+            .iter()
+            .flatten()
+            .find(|resource| resource._tag == THUMBNAIL_TAG)
+            .map_or(header_size, |resource| resource.offset as usize);
+        let thumbnail = self
+            .buf
+            .get(thumbnail_offset..thumbnail_offset + self.header.thumbnail_bytes())
+            .ok_or(VTFExtractError::UnexpectedEnd {
+                additional: self.header.thumbnail_bytes(),
+            })?;
+
+        let extra = self.extra_resources()?;
+
+        header.header_size = match version_minor {
+            0..=1 => 64,
+            2 => 80,
+            _ => 80 + 8 * (2 + extra.len() as u32),
+        };
+
+        let resources = match version_minor {
+            0..=2 => {
+                lost.extend(extra.iter().map(|(resource, data)| VTFResourceLoss {
+                    name: resource.name(),
+                    bytes: data.len(),
+                }));
+                None
+            }
+            _ => {
+                let mut resources = vec![
+                    VTFResourceEntryInfo {
+                        _tag: THUMBNAIL_TAG,
+                        flags: 0,
+                        offset: header.header_size,
+                    },
+                    VTFResourceEntryInfo {
+                        _tag: IMAGE_TAG,
+                        flags: 0,
+                        offset: header.header_size + thumbnail.len() as u32,
+                    },
+                ];
+
+                let mut offset = header.header_size + (thumbnail.len() + image.len()) as u32;
+                for (resource, data) in &extra {
+                    resources.push(VTFResourceEntryInfo {
+                        _tag: resource._tag,
+                        flags: resource.flags,
+                        offset: match resource.flags & NO_DATA {
+                            0 => offset,
+                            _ => resource.offset,
+                        },
+                    });
+                    offset += data.len() as u32;
+                }
+
+                Some(resources)
+            }
+        };
+
+        let mut buf = header.encode_with_resources(resources.as_deref())?;
+        buf.extend(thumbnail);
+        buf.extend(image);
+        if resources.is_some() {
+            for (_, data) in &extra {
+                buf.extend(*data);
+            }
+        }
+
+        Ok(VTFConversion { buf, lost })
+    }
+
+    pub(crate) fn extra_resources(&self) -> Result<Vec<(&VTFResourceEntryInfo, &[u8])>, VTFExtractError> {
+        self.resources
+            .iter()
+            .flatten()
+            .filter(|resource| resource._tag != THUMBNAIL_TAG && resource._tag != IMAGE_TAG)
+            .map(|resource| self.resource_data(resource).map(|data| (resource, data)))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn convert(&self, version_minor: u32, allow_loss: bool) -> Result<Vec<u8>, VTFEncodeError> {
+        let conversion = self.convert_version(version_minor)?;
+
+        if !allow_loss && !conversion.lost.is_empty() {
+            return Err(VTFEncodeError::LostResources {
+                resources: conversion.lost.iter().map(|loss| format!("{} ({} bytes)", loss.name, loss.bytes)).collect(),
+            });
+        }
+
+        Ok(conversion.buf)
+    }
+}
@@ -0,0 +1,161 @@
+use std::f32::consts::PI;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{flags, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat, VTF};
+
+fn face_direction(face: usize, u: f32, v: f32) -> [f32; 3] {
+    match face {
+        0 => [1.0, -v, -u],
+        1 => [-1.0, -v, u],
+        2 => [u, 1.0, v],
+        3 => [u, -1.0, -v],
+        4 => [u, -v, 1.0],
+        _ => [-u, -v, -1.0],
+    }
+}
+
+fn direction_face(direction: [f32; 3]) -> (usize, f32, f32) {
+    let [x, y, z] = direction;
+    let (face, major, u, v) = if x.abs() >= y.abs() && x.abs() >= z.abs() {
+        match x >= 0.0 {
+            true => (0, x, -z, -y),
+            false => (1, -x, z, -y),
+        }
+    } else if y.abs() >= z.abs() {
+        match y >= 0.0 {
+            true => (2, y, x, z),
+            false => (3, -y, x, -z),
+        }
+    } else {
+        match z >= 0.0 {
+            true => (4, z, x, -y),
+            false => (5, -z, -x, -y),
+        }
+    };
+
+    (face, (u / major + 1.0) / 2.0, (v / major + 1.0) / 2.0)
+}
+
+impl VTFData {
+    fn sample_bilinear(&self, u: f32, v: f32, wrap: bool) -> [u8; 4] {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (fx, fy) = (u * width as f32 - 0.5, (v * height as f32 - 0.5).clamp(0.0, (height - 1) as f32));
+        let (x0, y0) = (fx.floor() as isize, fy as isize);
+        let (tx, ty) = (fx - fx.floor(), fy.fract());
+
+        let texel = |x: isize, y: isize| {
+            let x = match wrap {
+                true => x.rem_euclid(width),
+                false => x.clamp(0, width - 1),
+            };
+            let i = ((y.min(height - 1) * width + x) * 4) as usize;
+            [0, 1, 2, 3].map(|c| self.rgba[i + c] as f32)
+        };
+
+        let (a, b, c, d) = (texel(x0, y0), texel(x0 + 1, y0), texel(x0, y0 + 1), texel(x0 + 1, y0 + 1));
+        [0, 1, 2, 3].map(|i| ((a[i] * (1.0 - tx) + b[i] * tx) * (1.0 - ty) + (c[i] * (1.0 - tx) + d[i] * tx) * ty).round() as u8)
+    }
+
+    fn cube_face(size: u16, direction: impl Fn(usize, usize) -> [u8; 4]) -> VTFData {
+        let rgba = (0..size as usize)
+            .flat_map(|y| (0..size as usize).map(move |x| (x, y)))
+            .flat_map(|(x, y)| direction(x, y))
+            .collect();
+
+        VTFData {
+            width: size,
+            height: size,
+            rgba,
+        }
+    }
+
+    pub fn equirectangular_to_cubemap(&self, size: u16) -> [VTFData; 6] {
+        [0, 1, 2, 3, 4, 5].map(|face| {
+            VTFData::cube_face(size, |x, y| {
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let [dx, dy, dz] = face_direction(face, u, v);
+
+                let longitude = dy.atan2(dx);
+                let latitude = (dz / (dx * dx + dy * dy + dz * dz).sqrt()).asin();
+                self.sample_bilinear(0.5 - longitude / (2.0 * PI), 0.5 - latitude / PI, true)
+            })
+        })
+    }
+
+    pub fn spheremap(faces: &[VTFData; 6]) -> VTFData {
+        let size = faces[0].width;
+
+        VTFData::cube_face(size, |x, y| {
+            let nx = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let ny = 1.0 - (y as f32 + 0.5) / size as f32 * 2.0;
+            let length = (nx * nx + ny * ny).sqrt().max(1.0);
+            let (nx, ny) = (nx / length, ny / length);
+            let nz = (1.0 - nx * nx - ny * ny).max(0.0).sqrt();
+
+            let reflection = [2.0 * nz * nx, 2.0 * nz * ny, 2.0 * nz * nz - 1.0];
+            let (face, u, v) = direction_face([-reflection[2], -reflection[0], reflection[1]]);
+            faces[face].sample_bilinear(u, v, false)
+        })
+    }
+}
+
+impl VTF {
+    pub fn cubemap(faces: [VTFData; 6], spheremap: bool, options: &VTFEncodeOptions) -> Result<Vec<u8>, VTFEncodeError> {
+        if let Some(face) = faces.iter().find(|face| face.width != face.height) {
+            return Err(VTFEncodeError::UnexpectedSize {
+                width: face.width,
+                height: face.width,
+                found_width: face.width,
+                found_height: face.height,
+            });
+        }
+
+        if spheremap && options.version_minor >= 5 {
+            return Err(VTFEncodeError::UnexpectedVersion {
+                version_minor: options.version_minor,
+            });
+        }
+
+        let mut images = faces.to_vec();
+        if spheremap {
+            images.push(VTFData::spheremap(&faces));
+        }
+
+        VTF::encode(
+            images,
+            &VTFEncodeOptions {
+                flags: options.flags | flags::ENVMAP,
+                first_frame: match (spheremap, options.version_minor) {
+                    (false, 0..=4) => 0xFFFF,
+                    _ => 0,
+                },
+                ..*options
+            },
+        )
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFCubemapFromEquirectangular")]
+pub fn vtf_cubemap_from_equirectangular(
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+    size: u16,
+    spheremap: bool,
+    format: VTFImageFormat,
+    version_minor: u32,
+) -> Result<Vec<u8>, VTFEncodeError> {
+    let panorama = VTFData::new(width, height, rgba)?;
+
+    VTF::cubemap(
+        panorama.equirectangular_to_cubemap(size),
+        spheremap,
+        &VTFEncodeOptions {
+            format,
+            version_minor,
+            ..Default::default()
+        },
+    )
+}
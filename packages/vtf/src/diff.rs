@@ -0,0 +1,196 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{VTFData, VTFExtractError, VTFMipMap, VTF};
+
+const SSIM_WINDOW: usize = 8;
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct VTFDiff {
+    pub header: Vec<VTFHeaderChange>,
+    pub flags_added: u32,
+    pub flags_removed: u32,
+    pub mipmaps: Vec<VTFMipMapDiff>,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFHeaderChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFMipMapDiff {
+    pub width: u16,
+    pub height: u16,
+    pub frame: usize,
+    pub changed_pixels: usize,
+    pub psnr: f64,
+    pub ssim: f64,
+}
+
+impl VTFData {
+    pub fn psnr(&self, other: &VTFData) -> f64 {
+        let squared_error = self.rgba.iter().zip(&other.rgba).map(|(a, b)| (*a as f64 - *b as f64).powi(2)).sum::<f64>();
+        let mse = squared_error / self.rgba.len().max(1) as f64;
+
+        match mse {
+            0.0 => f64::INFINITY,
+            _ => 10.0 * (255.0 * 255.0 / mse).log10(),
+        }
+    }
+
+    pub fn ssim(&self, other: &VTFData) -> f64 {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let luma = |data: &VTFData| {
+            data.rgba
+                .chunks_exact(4)
+                .map(|pixel| 0.2126 * pixel[0] as f64 + 0.7152 * pixel[1] as f64 + 0.0722 * pixel[2] as f64)
+                .collect::<Vec<f64>>()
+        };
+        let (a, b) = (luma(self), luma(other));
+
+        let (window_width, window_height) = (SSIM_WINDOW.min(width).max(1), SSIM_WINDOW.min(height).max(1));
+        let mut total = 0.0;
+        let mut windows = 0;
+
+        for top in (0..height.saturating_sub(window_height) + 1).step_by(window_height / 2 + 1) {
+            for left in (0..width.saturating_sub(window_width) + 1).step_by(window_width / 2 + 1) {
+                let indices = (top..top + window_height).flat_map(|y| (left..left + window_width).map(move |x| y * width + x));
+                let count = (window_width * window_height) as f64;
+
+                let (mut sum_a, mut sum_b, mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+                for i in indices {
+                    sum_a += a[i];
+                    sum_b += b[i];
+                    sum_aa += a[i] * a[i];
+                    sum_bb += b[i] * b[i];
+                    sum_ab += a[i] * b[i];
+                }
+
+                let (mean_a, mean_b) = (sum_a / count, sum_b / count);
+                let variance_a = sum_aa / count - mean_a * mean_a;
+                let variance_b = sum_bb / count - mean_b * mean_b;
+                let covariance = sum_ab / count - mean_a * mean_b;
+
+                total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+                    / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (variance_a + variance_b + SSIM_C2));
+                windows += 1;
+            }
+        }
+
+        if windows == 0 {
+            1.0
+        } else {
+            total / windows as f64
+        }
+    }
+
+    pub fn heatmap(&self, other: &VTFData) -> VTFData {
+        let rgba = self
+            .rgba
+            .chunks_exact(4)
+            .zip(other.rgba.chunks_exact(4))
+            .flat_map(|(a, b)| {
+                let difference = a.iter().zip(b).map(|(a, b)| a.abs_diff(*b)).max().unwrap_or(0) as u32;
+                let heat = difference * 3;
+                [
+                    heat.min(255) as u8,
+                    heat.saturating_sub(255).min(255) as u8,
+                    heat.saturating_sub(510).min(255) as u8,
+                    255,
+                ]
+            })
+            .collect();
+
+        VTFData {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+}
+
+impl VTF {
+    pub fn matching_mipmaps<'a>(&'a self, other: &'a VTF) -> Result<Vec<(usize, usize, &'a VTFMipMap)>, VTFExtractError> {
+        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
+        let other_mipmaps = other.mipmaps.as_ref().map_err(|err| err.clone())?;
+
+        Ok(mipmaps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, mipmap)| {
+                other_mipmaps
+                    .iter()
+                    .position(|other| other.width == mipmap.width && other.height == mipmap.height)
+                    .map(|other_index| (index, other_index, mipmap))
+            })
+            .collect())
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn diff(&self, other: &VTF) -> Result<VTFDiff, VTFExtractError> {
+        let (before, after) = (&self.header, &other.header);
+        let mut header = Vec::new();
+
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if format!("{:?}", before.$field) != format!("{:?}", after.$field) {
+                        header.push(VTFHeaderChange {
+                            field: stringify!($field).to_string(),
+                            before: format!("{:?}", before.$field),
+                            after: format!("{:?}", after.$field),
+                        });
+                    }
+                )*
+            };
+        }
+
+        compare!(
+            version_major,
+            version_minor,
+            width,
+            height,
+            flags,
+            frames,
+            first_frame,
+            bumpmap_scale,
+            high_res_image_format,
+            mipmap_count,
+            low_res_image_format,
+            low_res_image_width,
+            low_res_image_height
+        );
+
+        let mut mipmaps = Vec::new();
+        for (index, other_index, mipmap) in self.matching_mipmaps(other)? {
+            for frame in 0..before.frames.min(after.frames) as usize {
+                let (a, b) = (self.extract(index, frame)?, other.extract(other_index, frame)?);
+
+                mipmaps.push(VTFMipMapDiff {
+                    width: mipmap.width,
+                    height: mipmap.height,
+                    frame,
+                    changed_pixels: a.rgba.chunks_exact(4).zip(b.rgba.chunks_exact(4)).filter(|(a, b)| a != b).count(),
+                    psnr: a.psnr(&b),
+                    ssim: a.ssim(&b),
+                });
+            }
+        }
+
+        Ok(VTFDiff {
+            header,
+            flags_added: after.flags & !before.flags,
+            flags_removed: before.flags & !after.flags,
+            mipmaps,
+        })
+    }
+}
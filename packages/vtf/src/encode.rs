@@ -0,0 +1,370 @@
+use bincode::error::EncodeError;
+use thiserror::Error;
+use wasm_bindgen::{JsError, JsValue};
+
+use crate::{flags, VTFData, VTFExtractError, VTFHeader, VTFImageFormat, VTFResourceEntryInfo, VTFSignature, VTF};
+
+const THUMBNAIL_SIZE: u16 = 16;
+
+pub(crate) const THUMBNAIL_TAG: [u8; 3] = [0x01, 0, 0];
+pub(crate) const IMAGE_TAG: [u8; 3] = [0x30, 0, 0];
+pub(crate) const NO_DATA: u8 = 0x02;
+
+#[derive(Debug, Clone, Copy)]
+pub struct VTFEncodeOptions {
+    pub format: VTFImageFormat,
+    pub flags: u32,
+    pub version_minor: u32,
+    pub mipmaps: bool,
+    pub mipmap_count: Option<u8>,
+    pub first_frame: u16,
+    pub bumpmap_scale: f32,
+}
+
+impl Default for VTFEncodeOptions {
+    fn default() -> Self {
+        VTFEncodeOptions {
+            format: VTFImageFormat::DXT5,
+            flags: 0,
+            version_minor: 2,
+            mipmaps: true,
+            mipmap_count: None,
+            first_frame: 0,
+            bumpmap_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum VTFEncodeError {
+    #[error("{:#?}", self)]
+    FormatError(VTFImageFormat),
+
+    #[error("{:#?}", self)]
+    UnexpectedImageCount { faces: usize, found: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedSize {
+        width: u16,
+        height: u16,
+        found_width: u16,
+        found_height: u16,
+    },
+
+    #[error("{:#?}", self)]
+    UnexpectedDimensions { width: u16, height: u16 },
+
+    #[error("{:#?}", self)]
+    UnexpectedChannel { found: u8 },
+
+    #[error("{:#?}", self)]
+    UnexpectedVersion { version_minor: u32 },
+
+    #[error("{:#?}", self)]
+    UnexpectedFirstFrame { frames: u16, found: u16 },
+
+    #[error("{:#?}", self)]
+    LostResources { resources: Vec<String> },
+
+    #[error("{:#?}", self)]
+    UnexpectedLength { expected: usize, found: usize },
+
+    #[error(transparent)]
+    EncodeError(#[from] EncodeError),
+
+    #[error(transparent)]
+    ExtractError(#[from] VTFExtractError),
+}
+
+impl From<VTFImageFormat> for VTFEncodeError {
+    fn from(value: VTFImageFormat) -> Self {
+        VTFEncodeError::FormatError(value)
+    }
+}
+
+impl From<VTFEncodeError> for JsValue {
+    fn from(value: VTFEncodeError) -> Self {
+        JsValue::from(JsError::new(&format!("{:?}", value)))
+    }
+}
+
+impl VTFImageFormat {
+    pub fn encode(&self, rgba: &[u8], width: usize, height: usize) -> Result<Vec<u8>, VTFImageFormat> {
+        let mut out = vec![0; self.bytes(width, height)?];
+
+        match (self.compression(), self.texel_bytes()) {
+            (Some(compression), _) => {
+                let params = texpresso::Params::default();
+                match self {
+                    VTFImageFormat::DXT1 => {
+                        let opaque = rgba.chunks_exact(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255]).collect::<Vec<u8>>();
+                        compression.compress(&opaque, width, height, params, &mut out);
+                    }
+                    _ => compression.compress(rgba, width, height, params, &mut out),
+                }
+            }
+            (None, Some(texel_bytes)) => {
+                for (pixel, texel) in rgba.chunks_exact(4).zip(out.chunks_exact_mut(texel_bytes)) {
+                    self.encode_texel([pixel[0], pixel[1], pixel[2], pixel[3]], texel)?;
+                }
+            }
+            (None, None) => return Err(*self),
+        };
+
+        Ok(out)
+    }
+}
+
+impl VTFData {
+    pub fn new(width: u16, height: u16, rgba: Vec<u8>) -> Result<VTFData, VTFEncodeError> {
+        if width == 0 || height == 0 {
+            return Err(VTFEncodeError::UnexpectedDimensions { width, height });
+        }
+
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(VTFEncodeError::UnexpectedLength { expected, found: rgba.len() });
+        }
+
+        Ok(VTFData { width, height, rgba })
+    }
+
+    pub fn mipmap(&self, normal: bool) -> VTFData {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let (mip_width, mip_height) = ((width / 2).max(1), (height / 2).max(1));
+        let mut rgba = vec![0; mip_width * mip_height * 4];
+
+        for y in 0..mip_height {
+            for x in 0..mip_width {
+                let mut sum = [0u32; 4];
+                for (sx, sy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let i = (((y * 2 + sy).min(height - 1)) * width + (x * 2 + sx).min(width - 1)) * 4;
+                    for (c, sum) in sum.iter_mut().enumerate() {
+                        *sum += self.rgba[i + c] as u32;
+                    }
+                }
+
+                let mut pixel = sum.map(|sum| ((sum + 2) / 4) as u8);
+                if normal {
+                    let [x, y, z] = [0, 1, 2].map(|c| pixel[c] as f32 / 127.5 - 1.0);
+                    let length = (x * x + y * y + z * z).sqrt().max(f32::EPSILON);
+                    for (c, value) in [x, y, z].iter().enumerate() {
+                        pixel[c] = ((value / length * 0.5 + 0.5) * 255.0).round() as u8;
+                    }
+                }
+
+                rgba[(y * mip_width + x) * 4..][..4].copy_from_slice(&pixel);
+            }
+        }
+
+        VTFData {
+            width: mip_width as u16,
+            height: mip_height as u16,
+            rgba,
+        }
+    }
+
+    pub fn mipmaps(self, count: u8, normal: bool) -> Vec<VTFData> {
+        let mut mipmaps = vec![self];
+        for _ in 1..count {
+            let mipmap = mipmaps[mipmaps.len() - 1].mipmap(normal);
+            mipmaps.push(mipmap);
+        }
+        mipmaps
+    }
+}
+
+impl VTFHeader {
+    pub(crate) fn encode_with_resources(&self, resources: Option<&[VTFResourceEntryInfo]>) -> Result<Vec<u8>, EncodeError> {
+        let config = bincode::config::standard().with_fixed_int_encoding();
+        let mut buf = bincode::encode_to_vec(self, config)?;
+
+        if self.version_minor >= 2 {
+            buf.extend(bincode::encode_to_vec(1u16, config)?);
+        }
+
+        if let Some(resources) = resources {
+            buf.extend([0; 3]);
+            buf.extend(bincode::encode_to_vec(resources.len() as u32, config)?);
+        }
+
+        buf.resize(80.min(self.header_size as usize), 0);
+
+        for resource in resources.unwrap_or_default() {
+            buf.extend(bincode::encode_to_vec(resource, config)?);
+        }
+
+        Ok(buf)
+    }
+}
+
+pub(crate) fn full_mipmap_count(width: u16, height: u16) -> u8 {
+    (u16::BITS - width.max(height).leading_zeros()) as u8
+}
+
+impl VTF {
+    pub fn encode_options(&self) -> VTFEncodeOptions {
+        VTFEncodeOptions {
+            format: self.header.high_res_image_format,
+            flags: self.header.flags,
+            version_minor: self.header.version_minor,
+            mipmaps: true,
+            mipmap_count: Some(self.header.mipmap_count),
+            first_frame: self.header.first_frame,
+            bumpmap_scale: self.header.bumpmap_scale,
+        }
+    }
+
+    pub fn encode(images: Vec<VTFData>, options: &VTFEncodeOptions) -> Result<Vec<u8>, VTFEncodeError> {
+        VTF::encode_with_extra(images, options, &[])
+    }
+
+    pub fn encode_with_extra(images: Vec<VTFData>, options: &VTFEncodeOptions, extra: &[(&VTFResourceEntryInfo, &[u8])]) -> Result<Vec<u8>, VTFEncodeError> {
+        if options.version_minor > 5 {
+            return Err(VTFEncodeError::UnexpectedVersion {
+                version_minor: options.version_minor,
+            });
+        }
+
+        if options.version_minor < 3 && !extra.is_empty() {
+            return Err(VTFEncodeError::LostResources {
+                resources: extra
+                    .iter()
+                    .map(|(resource, data)| format!("{} ({} bytes)", resource.name(), data.len()))
+                    .collect(),
+            });
+        }
+
+        let Some(first) = images.first() else {
+            return Err(VTFEncodeError::UnexpectedImageCount { faces: 1, found: 0 });
+        };
+        let (width, height) = (first.width, first.height);
+
+        if width == 0 || height == 0 {
+            return Err(VTFEncodeError::UnexpectedDimensions { width, height });
+        }
+
+        if let Some(image) = images.iter().find(|image| image.width != width || image.height != height) {
+            return Err(VTFEncodeError::UnexpectedSize {
+                width,
+                height,
+                found_width: image.width,
+                found_height: image.height,
+            });
+        }
+
+        let mipmap_count = match (options.mipmaps, options.mipmap_count) {
+            (false, _) => 1,
+            (true, None) => full_mipmap_count(width, height),
+            (true, Some(count)) => count.clamp(1, full_mipmap_count(width, height)),
+        };
+        let normal = options.flags & flags::NORMAL != 0;
+
+        let thumbnail_shift = (0..u16::BITS)
+            .find(|i| (width >> i).max(1) <= THUMBNAIL_SIZE && (height >> i).max(1) <= THUMBNAIL_SIZE)
+            .unwrap_or(0);
+
+        let header_size = match options.version_minor {
+            0..=1 => 64,
+            2 => 80,
+            _ => 80 + 8 * (2 + extra.len() as u32),
+        };
+
+        let pixels = (first.rgba.len() / 4).max(1) as f32;
+        let reflectivity = [0, 1, 2].map(|c| first.rgba.chunks_exact(4).map(|pixel| pixel[c] as f32 / 255.0).sum::<f32>() / pixels);
+
+        let mut header = VTFHeader {
+            signature: VTFSignature,
+            version_major: 7,
+            version_minor: options.version_minor,
+            header_size,
+            width,
+            height,
+            flags: options.flags,
+            frames: 0,
+            first_frame: options.first_frame,
+            _padding0: [0; 4],
+            _reflectivity: reflectivity,
+            _padding1: [0; 4],
+            bumpmap_scale: options.bumpmap_scale,
+            high_res_image_format: options.format,
+            mipmap_count,
+            low_res_image_format: VTFImageFormat::DXT1,
+            low_res_image_width: (width >> thumbnail_shift).max(1) as u8,
+            low_res_image_height: (height >> thumbnail_shift).max(1) as u8,
+        };
+
+        let faces = header.faces();
+        if !images.len().is_multiple_of(faces) || images.len() / faces > u16::MAX as usize {
+            return Err(VTFEncodeError::UnexpectedImageCount { faces, found: images.len() });
+        }
+        header.frames = (images.len() / faces) as u16;
+
+        if options.first_frame >= header.frames && !(faces > 1 && options.first_frame == 0xFFFF) {
+            return Err(VTFEncodeError::UnexpectedFirstFrame {
+                frames: header.frames,
+                found: options.first_frame,
+            });
+        }
+
+        let chains = images
+            .into_iter()
+            .map(|image| image.mipmaps(mipmap_count, normal))
+            .collect::<Vec<Vec<VTFData>>>();
+
+        let thumbnail = {
+            let mut thumbnail = chains[0][0].clone();
+            while thumbnail.width > header.low_res_image_width as u16 || thumbnail.height > header.low_res_image_height as u16 {
+                thumbnail = thumbnail.mipmap(false);
+            }
+            VTFImageFormat::DXT1.encode(&thumbnail.rgba, thumbnail.width as usize, thumbnail.height as usize)?
+        };
+
+        let mut image = Vec::new();
+        for i in (0..mipmap_count as usize).rev() {
+            for chain in &chains {
+                let mipmap = &chain[i];
+                image.extend(options.format.encode(&mipmap.rgba, mipmap.width as usize, mipmap.height as usize)?);
+            }
+        }
+
+        let resources = (options.version_minor >= 3).then(|| {
+            let mut resources = vec![
+                VTFResourceEntryInfo {
+                    _tag: THUMBNAIL_TAG,
+                    flags: 0,
+                    offset: header_size,
+                },
+                VTFResourceEntryInfo {
+                    _tag: IMAGE_TAG,
+                    flags: 0,
+                    offset: header_size + thumbnail.len() as u32,
+                },
+            ];
+
+            let mut offset = header_size + (thumbnail.len() + image.len()) as u32;
+            for (resource, data) in extra {
+                resources.push(VTFResourceEntryInfo {
+                    _tag: resource._tag,
+                    flags: resource.flags,
+                    offset: match resource.flags & NO_DATA {
+                        0 => offset,
+                        _ => resource.offset,
+                    },
+                });
+                offset += data.len() as u32;
+            }
+
+            resources
+        });
+
+        let mut buf = header.encode_with_resources(resources.as_deref())?;
+        buf.extend(thumbnail);
+        buf.extend(image);
+        for (_, data) in extra {
+            buf.extend(*data);
+        }
+
+        Ok(buf)
+    }
+}
@@ -0,0 +1,24 @@
+pub const POINT_SAMPLE: u32 = 1 << 0;
+pub const TRILINEAR: u32 = 1 << 1;
+pub const CLAMP_S: u32 = 1 << 2;
+pub const CLAMP_T: u32 = 1 << 3;
+pub const ANISOTROPIC: u32 = 1 << 4;
+pub const HINT_DXT5: u32 = 1 << 5;
+pub const SRGB: u32 = 1 << 6;
+pub const NORMAL: u32 = 1 << 7;
+pub const NOMIP: u32 = 1 << 8;
+pub const NOLOD: u32 = 1 << 9;
+pub const ALL_MIPS: u32 = 1 << 10;
+pub const PROCEDURAL: u32 = 1 << 11;
+pub const ONEBITALPHA: u32 = 1 << 12;
+pub const EIGHTBITALPHA: u32 = 1 << 13;
+pub const ENVMAP: u32 = 1 << 14;
+pub const RENDERTARGET: u32 = 1 << 15;
+pub const DEPTHRENDERTARGET: u32 = 1 << 16;
+pub const NODEBUGOVERRIDE: u32 = 1 << 17;
+pub const SINGLECOPY: u32 = 1 << 18;
+pub const NODEPTHBUFFER: u32 = 1 << 23;
+pub const CLAMP_U: u32 = 1 << 25;
+pub const VERTEXTEXTURE: u32 = 1 << 26;
+pub const SSBUMP: u32 = 1 << 27;
+pub const BORDER: u32 = 1 << 29;
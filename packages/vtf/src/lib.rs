@@ -1,14 +1,47 @@
-use std::io::{BufReader, Cursor, Seek, SeekFrom};
+use std::ops::Deref;
 
 use bincode::{
     de::Decoder,
-    error::{AllowedEnumVariants, DecodeError},
-    impl_borrow_decode, Decode,
+    enc::Encoder,
+    error::{AllowedEnumVariants, DecodeError, EncodeError},
+    impl_borrow_decode, Decode, Encode,
 };
 use derive_more::Display;
 use thiserror::Error;
 use wasm_bindgen::{prelude::wasm_bindgen, JsError, JsValue};
 
+mod budget;
+mod convert;
+mod cubemap;
+mod diff;
+mod encode;
+pub mod flags;
+mod lint;
+mod normal;
+mod pixels;
+mod probe;
+mod sheet;
+mod ssbump;
+mod stats;
+mod texel;
+mod transform;
+mod view;
+
+pub use budget::{VTFBudget, VTFFormatBudget, VTFMipMapBudget};
+pub use convert::{VTFConversion, VTFResourceLoss};
+pub use cubemap::vtf_cubemap_from_equirectangular;
+pub use diff::{VTFDiff, VTFHeaderChange, VTFMipMapDiff};
+pub use encode::{VTFEncodeError, VTFEncodeOptions};
+pub use lint::{VTFLint, VTFLintKind, VTFLints};
+pub use normal::{vtf_from_height_map, VTFNormalKernel, VTFWrap};
+pub use pixels::{linear_to_srgb, srgb_to_linear, VTFColourSpace, VTFImage, VTFPixelType, VTFPixels};
+pub use probe::{vtf_probe, PROBE_BYTES};
+pub use ssbump::{vtf_ssbump_from_height_map, BUMP_BASIS};
+pub use stats::{VTFAlphaUsage, VTFChannelStatistics, VTFStatistics};
+pub use texel::{VTFBlockAlpha, VTFRawTexel, VTFTexel};
+pub use transform::{vtf_pack_channels, VTFTransform, VTFTransforms};
+pub use view::{VTFPixelBuffer, VTFRef};
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -21,20 +54,18 @@ macro_rules! console_log {
 
 #[wasm_bindgen]
 #[derive(Debug)]
-pub struct VTF {
-    #[wasm_bindgen(skip)]
-    pub buf: Vec<u8>,
-    pub header: VTFHeader,
+pub struct VTF(VTFRef<'static>);
 
-    #[wasm_bindgen(skip)]
-    pub resources: Option<Vec<VTFResourceEntryInfo>>,
+impl Deref for VTF {
+    type Target = VTFRef<'static>;
 
-    #[wasm_bindgen(skip)]
-    pub mipmaps: Result<Vec<VTFMipMap>, VTFExtractError>,
+    fn deref(&self) -> &VTFRef<'static> {
+        &self.0
+    }
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone, Copy, Decode)]
+#[derive(Debug, Clone, Copy, Decode, Encode)]
 pub struct VTFHeader {
     pub signature: VTFSignature,
     pub version_major: u32,
@@ -56,6 +87,27 @@ pub struct VTFHeader {
     pub low_res_image_height: u8,
 }
 
+impl VTFHeader {
+    pub fn faces(&self) -> usize {
+        match self.flags & flags::ENVMAP {
+            0 => 1,
+            _ if self.version_minor < 5 && self.first_frame != 0xFFFF => 7,
+            _ => 6,
+        }
+    }
+
+    pub fn thumbnail_bytes(&self) -> usize {
+        texpresso::Format::Bc1.compressed_size(self.low_res_image_width as usize, self.low_res_image_height as usize)
+    }
+
+    pub fn image_bytes(&self, format: VTFImageFormat, mipmap_count: u8) -> Result<usize, VTFImageFormat> {
+        (0..mipmap_count)
+            .map(|i| format.bytes((self.width as usize >> i).max(1), (self.height as usize >> i).max(1)))
+            .sum::<Result<usize, VTFImageFormat>>()
+            .map(|bytes| bytes * self.faces() * self.frames as usize)
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, Display)]
 #[display("VTF\0")]
@@ -72,6 +124,12 @@ impl<Context> Decode<Context> for VTFSignature {
 
 impl_borrow_decode!(VTFSignature);
 
+impl Encode for VTFSignature {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        b"VTF\0".encode(encoder)
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
@@ -149,7 +207,43 @@ impl<Context> Decode<Context> for VTFImageFormat {
 
 impl_borrow_decode!(VTFImageFormat);
 
+impl Encode for VTFImageFormat {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (*self as i32).encode(encoder)
+    }
+}
+
 impl VTFImageFormat {
+    pub const FORMATS: [VTFImageFormat; 27] = [
+        VTFImageFormat::RGBA8888,
+        VTFImageFormat::ABGR8888,
+        VTFImageFormat::RGB888,
+        VTFImageFormat::BGR888,
+        VTFImageFormat::RGB565,
+        VTFImageFormat::I8,
+        VTFImageFormat::IA88,
+        VTFImageFormat::P8,
+        VTFImageFormat::A8,
+        VTFImageFormat::RGB888BlueScreen,
+        VTFImageFormat::BGR888BlueScreen,
+        VTFImageFormat::ARGB8888,
+        VTFImageFormat::BGRA8888,
+        VTFImageFormat::DXT1,
+        VTFImageFormat::DXT3,
+        VTFImageFormat::DXT5,
+        VTFImageFormat::BGRX8888,
+        VTFImageFormat::BGR565,
+        VTFImageFormat::BGRX5551,
+        VTFImageFormat::BGRA4444,
+        VTFImageFormat::DXT1OneBitAlpha,
+        VTFImageFormat::BGRA5551,
+        VTFImageFormat::UV88,
+        VTFImageFormat::UVWQ8888,
+        VTFImageFormat::RGBA16161616F,
+        VTFImageFormat::RGBA16161616,
+        VTFImageFormat::UVLX8888,
+    ];
+
     pub fn bytes(&self, width: usize, height: usize) -> Result<usize, VTFImageFormat> {
         match self {
             VTFImageFormat::None => Err(VTFImageFormat::None),
@@ -157,9 +251,9 @@ impl VTFImageFormat {
             VTFImageFormat::ABGR8888 => Ok(width * height * 4),
             VTFImageFormat::RGB888 => Ok(width * height * 3),
             VTFImageFormat::BGR888 => Ok(width * height * 3),
-            VTFImageFormat::RGB565 => Err(VTFImageFormat::RGB565),
+            VTFImageFormat::RGB565 => Ok(width * height * 2),
             VTFImageFormat::I8 => Ok(width * height),
-            VTFImageFormat::IA88 => Err(VTFImageFormat::IA88),
+            VTFImageFormat::IA88 => Ok(width * height * 2),
             VTFImageFormat::P8 => Err(VTFImageFormat::P8),
             VTFImageFormat::A8 => Ok(width * height),
             VTFImageFormat::RGB888BlueScreen => Ok(width * height * 3),
@@ -170,21 +264,177 @@ impl VTFImageFormat {
             VTFImageFormat::DXT3 => Ok(texpresso::Format::Bc2.compressed_size(width, height)),
             VTFImageFormat::DXT5 => Ok(texpresso::Format::Bc3.compressed_size(width, height)),
             VTFImageFormat::BGRX8888 => Ok(width * height * 4),
-            VTFImageFormat::BGR565 => Err(VTFImageFormat::BGR565),
-            VTFImageFormat::BGRX5551 => Err(VTFImageFormat::BGRX5551),
-            VTFImageFormat::BGRA4444 => Err(VTFImageFormat::BGRA4444),
-            VTFImageFormat::DXT1OneBitAlpha => Err(VTFImageFormat::DXT1OneBitAlpha),
-            VTFImageFormat::BGRA5551 => Err(VTFImageFormat::BGRA5551),
-            VTFImageFormat::UV88 => Err(VTFImageFormat::UV88),
-            VTFImageFormat::UVWQ8888 => Err(VTFImageFormat::UVWQ8888),
-            VTFImageFormat::RGBA16161616F => Err(VTFImageFormat::RGBA16161616F),
-            VTFImageFormat::RGBA16161616 => Err(VTFImageFormat::RGBA16161616),
-            VTFImageFormat::UVLX8888 => Err(VTFImageFormat::UVLX8888),
+            VTFImageFormat::BGR565 => Ok(width * height * 2),
+            VTFImageFormat::BGRX5551 => Ok(width * height * 2),
+            VTFImageFormat::BGRA4444 => Ok(width * height * 2),
+            VTFImageFormat::DXT1OneBitAlpha => Ok(texpresso::Format::Bc1.compressed_size(width, height)),
+            VTFImageFormat::BGRA5551 => Ok(width * height * 2),
+            VTFImageFormat::UV88 => Ok(width * height * 2),
+            VTFImageFormat::UVWQ8888 => Ok(width * height * 4),
+            VTFImageFormat::RGBA16161616F => Ok(width * height * 8),
+            VTFImageFormat::RGBA16161616 => Ok(width * height * 8),
+            VTFImageFormat::UVLX8888 => Ok(width * height * 4),
+        }
+    }
+
+    pub fn compression(&self) -> Option<texpresso::Format> {
+        match self {
+            VTFImageFormat::DXT1 | VTFImageFormat::DXT1OneBitAlpha => Some(texpresso::Format::Bc1),
+            VTFImageFormat::DXT3 => Some(texpresso::Format::Bc2),
+            VTFImageFormat::DXT5 => Some(texpresso::Format::Bc3),
+            _ => None,
+        }
+    }
+
+    pub fn texel_bytes(&self) -> Option<usize> {
+        match self.compression() {
+            Some(_) => None,
+            None => self.bytes(1, 1).ok(),
+        }
+    }
+
+    pub fn decode_texel(&self, texel: &[u8]) -> Result<[u8; 4], VTFImageFormat> {
+        let rgb565 = |value: u16| [expand::<5>(value & 0x1F), expand::<6>((value >> 5) & 0x3F), expand::<5>(value >> 11)];
+        let le16 = |i: usize| u16::from_le_bytes([texel[i], texel[i + 1]]);
+
+        match self {
+            VTFImageFormat::RGBA8888 => Ok([texel[0], texel[1], texel[2], texel[3]]),
+            VTFImageFormat::ABGR8888 => Ok([texel[3], texel[2], texel[1], texel[0]]),
+            VTFImageFormat::RGB888 => Ok([texel[0], texel[1], texel[2], 255]),
+            VTFImageFormat::BGR888 => Ok([texel[2], texel[1], texel[0], 255]),
+            VTFImageFormat::RGB565 => {
+                let [r, g, b] = rgb565(le16(0));
+                Ok([r, g, b, 255])
+            }
+            VTFImageFormat::I8 => Ok([texel[0], texel[0], texel[0], 255]),
+            VTFImageFormat::IA88 => Ok([texel[0], texel[0], texel[0], texel[1]]),
+            VTFImageFormat::A8 => Ok([0, 0, 0, texel[0]]),
+            VTFImageFormat::RGB888BlueScreen => match texel {
+                [0, 0, 255] => Ok([0, 0, 0, 0]),
+                _ => Ok([texel[0], texel[1], texel[2], 255]),
+            },
+            VTFImageFormat::BGR888BlueScreen => match texel {
+                [255, 0, 0] => Ok([0, 0, 0, 0]),
+                _ => Ok([texel[2], texel[1], texel[0], 255]),
+            },
+            VTFImageFormat::ARGB8888 => Ok([texel[1], texel[2], texel[3], texel[0]]),
+            VTFImageFormat::BGRA8888 => Ok([texel[2], texel[1], texel[0], texel[3]]),
+            VTFImageFormat::BGRX8888 => Ok([texel[2], texel[1], texel[0], 255]),
+            VTFImageFormat::BGR565 => {
+                let [b, g, r] = rgb565(le16(0));
+                Ok([r, g, b, 255])
+            }
+            VTFImageFormat::BGRX5551 | VTFImageFormat::BGRA5551 => {
+                let value = le16(0);
+                let alpha = match self {
+                    VTFImageFormat::BGRA5551 if value >> 15 == 0 => 0,
+                    _ => 255,
+                };
+                Ok([
+                    expand::<5>((value >> 10) & 0x1F),
+                    expand::<5>((value >> 5) & 0x1F),
+                    expand::<5>(value & 0x1F),
+                    alpha,
+                ])
+            }
+            VTFImageFormat::BGRA4444 => {
+                let value = le16(0);
+                Ok([
+                    expand::<4>((value >> 8) & 0xF),
+                    expand::<4>((value >> 4) & 0xF),
+                    expand::<4>(value & 0xF),
+                    expand::<4>(value >> 12),
+                ])
+            }
+            VTFImageFormat::UV88 => Ok([texel[0], texel[1], 0, 255]),
+            VTFImageFormat::UVWQ8888 | VTFImageFormat::UVLX8888 => Ok([texel[0], texel[1], texel[2], texel[3]]),
+            VTFImageFormat::RGBA16161616F => Ok([0, 2, 4, 6].map(|i| (f16_to_f32(le16(i)).clamp(0.0, 1.0) * 255.0).round() as u8)),
+            VTFImageFormat::RGBA16161616 => Ok([0, 2, 4, 6].map(|i| (le16(i) >> 8) as u8)),
+            format => Err(*format),
         }
     }
+
+    pub fn encode_texel(&self, rgba: [u8; 4], texel: &mut [u8]) -> Result<(), VTFImageFormat> {
+        let [r, g, b, a] = rgba;
+        let rgb565 = |x: u8, y: u8, z: u8| (x as u16 >> 3) | (y as u16 >> 2) << 5 | (z as u16 >> 3) << 11;
+
+        match self {
+            VTFImageFormat::RGBA8888 => texel.copy_from_slice(&[r, g, b, a]),
+            VTFImageFormat::ABGR8888 => texel.copy_from_slice(&[a, b, g, r]),
+            VTFImageFormat::RGB888 => texel.copy_from_slice(&[r, g, b]),
+            VTFImageFormat::BGR888 => texel.copy_from_slice(&[b, g, r]),
+            VTFImageFormat::RGB565 => texel.copy_from_slice(&rgb565(r, g, b).to_le_bytes()),
+            VTFImageFormat::I8 => texel[0] = ((r as u16 + g as u16 + b as u16) / 3) as u8,
+            VTFImageFormat::IA88 => texel.copy_from_slice(&[((r as u16 + g as u16 + b as u16) / 3) as u8, a]),
+            VTFImageFormat::A8 => texel[0] = a,
+            VTFImageFormat::RGB888BlueScreen => texel.copy_from_slice(&if a < 128 { [0, 0, 255] } else { [r, g, b] }),
+            VTFImageFormat::BGR888BlueScreen => texel.copy_from_slice(&if a < 128 { [255, 0, 0] } else { [b, g, r] }),
+            VTFImageFormat::ARGB8888 => texel.copy_from_slice(&[a, r, g, b]),
+            VTFImageFormat::BGRA8888 => texel.copy_from_slice(&[b, g, r, a]),
+            VTFImageFormat::BGRX8888 => texel.copy_from_slice(&[b, g, r, 255]),
+            VTFImageFormat::BGR565 => texel.copy_from_slice(&rgb565(b, g, r).to_le_bytes()),
+            VTFImageFormat::BGRX5551 | VTFImageFormat::BGRA5551 => {
+                let alpha = match self {
+                    VTFImageFormat::BGRA5551 if a < 128 => 0,
+                    _ => 1,
+                };
+                texel.copy_from_slice(&((b as u16 >> 3) | (g as u16 >> 3) << 5 | (r as u16 >> 3) << 10 | alpha << 15).to_le_bytes())
+            }
+            VTFImageFormat::BGRA4444 => {
+                texel.copy_from_slice(&((b as u16 >> 4) | (g as u16 >> 4) << 4 | (r as u16 >> 4) << 8 | (a as u16 >> 4) << 12).to_le_bytes())
+            }
+            VTFImageFormat::UV88 => texel.copy_from_slice(&[r, g]),
+            VTFImageFormat::UVWQ8888 | VTFImageFormat::UVLX8888 => texel.copy_from_slice(&rgba),
+            VTFImageFormat::RGBA16161616F => {
+                for (i, value) in rgba.iter().enumerate() {
+                    texel[i * 2..i * 2 + 2].copy_from_slice(&f32_to_f16(*value as f32 / 255.0).to_le_bytes());
+                }
+            }
+            VTFImageFormat::RGBA16161616 => {
+                for (i, value) in rgba.iter().enumerate() {
+                    texel[i * 2..i * 2 + 2].copy_from_slice(&(*value as u16 * 257).to_le_bytes());
+                }
+            }
+            format => return Err(*format),
+        };
+
+        Ok(())
+    }
+}
+
+pub(crate) fn expand<const BITS: u32>(value: u16) -> u8 {
+    ((value as u32 * 255 + ((1 << BITS) - 1) / 2) / ((1 << BITS) - 1)) as u8
+}
+
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32 - 127 + 15;
+    let mantissa = bits & 0x7F_FFFF;
+
+    match exponent {
+        _ if value.is_nan() => 0x7E00,
+        ..=0 if exponent < -10 => sign,
+        ..=0 => sign | (((mantissa | 0x80_0000) >> (14 - exponent)) as u16),
+        0x1F.. => sign | 0x7C00,
+        _ => sign | (exponent as u16) << 10 | (mantissa >> 13) as u16,
+    }
+}
+
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits >> 15 == 0 { 1.0 } else { -1.0 };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f32;
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1F if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1F => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
 }
 
-#[derive(Debug, Decode)]
+#[derive(Debug, Decode, Encode)]
 pub struct VTFResourceEntryInfo {
     _tag: [u8; 3],
     pub flags: u8,
@@ -195,6 +445,7 @@ pub struct VTFResourceEntryInfo {
 pub struct VTFMipMap {
     pub width: u16,
     pub height: u16,
+    pub faces: usize,
     pub frames: Vec<VTFFrame>,
 }
 
@@ -214,7 +465,7 @@ impl From<VTFError> for JsValue {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VTFData {
     pub width: u16,
     pub height: u16,
@@ -232,8 +483,20 @@ pub enum VTFExtractError {
     #[error("{:#?}", self)]
     UnexpectedFrame { frame_count: u16, found: usize },
 
+    #[error("{:#?}", self)]
+    UnexpectedFace { face_count: usize, found: usize },
+
     #[error("{:#?}", self)]
     UnexpectedEnd { additional: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedTexel { width: u16, height: u16, x: u16, y: u16 },
+
+    #[error("{:#?}", self)]
+    UnexpectedSize { width: usize, height: usize },
+
+    #[error("{:#?}", self)]
+    UnexpectedBufferLength { expected: usize, found: usize },
 }
 
 impl From<VTFImageFormat> for VTFExtractError {
@@ -250,228 +513,17 @@ impl From<VTFExtractError> for JsValue {
 
 #[wasm_bindgen]
 impl VTF {
+    /// wasm-bindgen copies `buf` into linear memory once, everything after that borrows it.
     #[wasm_bindgen(constructor)]
     pub fn new(buf: Vec<u8>) -> Result<VTF, VTFError> {
-        let mut reader = BufReader::new(Cursor::new(&buf));
-        let config = bincode::config::standard().with_fixed_int_encoding();
-
-        let header: VTFHeader = bincode::decode_from_reader(&mut reader, config)?;
-        console_log!("{:#?}", header);
-
-        if header.version_major >= 7 && header.version_minor >= 2 {
-            let _depth: u16 = bincode::decode_from_reader(&mut reader, config)?;
-        }
-
-        let num_resources_option = if header.version_major >= 7 && header.version_minor >= 3 {
-            let _padding2: [u8; 3] = bincode::decode_from_reader(&mut reader, config)?;
-            let num_resources: u32 = bincode::decode_from_reader(&mut reader, config)?;
-            Some(num_resources)
-        } else {
-            None
-        };
-
-        let _padding3: [u8; 8] = bincode::decode_from_reader(&mut reader, config)?;
+        let vtf = VTF(VTFRef::new(buf)?);
+        console_log!("{:#?}", vtf.header);
 
-        let resources = match num_resources_option {
-            Some(num_resources) => Some(
-                (0..num_resources)
-                    .map(|_| bincode::decode_from_reader::<VTFResourceEntryInfo, _, _>(&mut reader, config))
-                    .collect::<Result<Vec<VTFResourceEntryInfo>, DecodeError>>()?,
-            ),
-            None => None,
-        };
-
-        reader
-            .seek(SeekFrom::Start(header.header_size as u64))
-            .map_err(|_| DecodeError::UnexpectedEnd {
-                additional: header.header_size as usize,
-            })?;
-
-        let thumbnail_bytes = texpresso::Format::Bc1.compressed_size(header.low_res_image_width as usize, header.low_res_image_height as usize);
-        reader
-            .seek_relative(thumbnail_bytes as i64)
-            .map_err(|_| DecodeError::UnexpectedEnd { additional: thumbnail_bytes })?;
-
-        let mipmaps = (0..header.mipmap_count)
-            .rev()
-            .map(|i| -> Result<VTFMipMap, VTFExtractError> {
-                let width = (header.width as usize >> i).max(1);
-                let height = (header.height as usize >> i).max(1);
-
-                let bytes = header.high_res_image_format.bytes(width, height)?;
-
-                let frames = (0..header.frames)
-                    .map(|_| {
-                        let offset = reader.stream_position().unwrap() as usize;
-                        reader
-                            .seek_relative(bytes as i64)
-                            .map_err(|_| VTFExtractError::UnexpectedEnd { additional: bytes })?;
-                        Ok(VTFFrame { offset, bytes })
-                    })
-                    .collect::<Result<Vec<VTFFrame>, VTFExtractError>>()?;
-
-                Ok(VTFMipMap {
-                    width: width as u16,
-                    height: height as u16,
-                    frames,
-                })
-            })
-            .collect::<Result<Vec<VTFMipMap>, VTFExtractError>>();
-
-        Ok(VTF {
-            buf,
-            header,
-            resources,
-            mipmaps,
-        })
+        Ok(vtf)
     }
-}
-
-impl VTF {
-    pub fn extract(&self, mipmap_index: usize, frame_index: usize) -> Result<VTFData, VTFExtractError> {
-        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
-
-        let mipmap = mipmaps.get(mipmap_index).ok_or(VTFExtractError::UnexpectedMipMap {
-            mipmap_count: self.header.mipmap_count,
-            found: mipmap_index,
-        })?;
-
-        let frame = mipmap.frames.get(frame_index).ok_or(VTFExtractError::UnexpectedFrame {
-            frame_count: self.header.frames,
-            found: frame_index,
-        })?;
-
-        let buf = self
-            .buf
-            .get(frame.offset..(frame.offset + frame.bytes))
-            .ok_or(VTFExtractError::UnexpectedEnd { additional: frame.bytes })?;
-
-        let mut rgba = vec![0; mipmap.width as usize * mipmap.height as usize * 4];
-
-        match self.header.high_res_image_format {
-            VTFImageFormat::RGBA8888 => {
-                rgba.copy_from_slice(buf);
-            }
-            VTFImageFormat::ABGR8888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(4) {
-                    rgba[i] = chunk[3];
-                    rgba[i + 1] = chunk[2];
-                    rgba[i + 2] = chunk[1];
-                    rgba[i + 3] = chunk[0];
-                    i += 4;
-                }
-            }
-            VTFImageFormat::RGB888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(3) {
-                    rgba[i] = chunk[0];
-                    rgba[i + 1] = chunk[1];
-                    rgba[i + 2] = chunk[2];
-                    rgba[i + 3] = 255;
-                    i += 4;
-                }
-            }
-            VTFImageFormat::BGR888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(3) {
-                    rgba[i] = chunk[2];
-                    rgba[i + 1] = chunk[1];
-                    rgba[i + 2] = chunk[0];
-                    rgba[i + 3] = 255;
-                    i += 4;
-                }
-            }
-            VTFImageFormat::I8 => {
-                let mut i = 0;
-                for byte in buf {
-                    rgba[i] = *byte;
-                    rgba[i + 1] = *byte;
-                    rgba[i + 2] = *byte;
-                    rgba[i + 3] = 255;
-                    i += 4;
-                }
-            }
-            VTFImageFormat::A8 => {
-                let mut i = 0;
-                for byte in buf {
-                    rgba[i] = 0;
-                    rgba[i + 1] = 0;
-                    rgba[i + 2] = 0;
-                    rgba[i + 3] = *byte;
-                    i += 4;
-                }
-            }
-            VTFImageFormat::RGB888BlueScreen => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(3) {
-                    if chunk != [0, 0, 255] {
-                        rgba[i] = chunk[0];
-                        rgba[i + 1] = chunk[1];
-                        rgba[i + 2] = chunk[2];
-                        rgba[i + 3] = 255;
-                    }
-                    i += 4;
-                }
-            }
-            VTFImageFormat::BGR888BlueScreen => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(3) {
-                    if chunk != [255, 0, 0] {
-                        rgba[i] = chunk[2];
-                        rgba[i + 1] = chunk[1];
-                        rgba[i + 2] = chunk[0];
-                        rgba[i + 3] = 255;
-                    }
-                    i += 4;
-                }
-            }
-            VTFImageFormat::ARGB8888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(4) {
-                    rgba[i] = chunk[1];
-                    rgba[i + 1] = chunk[2];
-                    rgba[i + 2] = chunk[3];
-                    rgba[i + 3] = chunk[0];
-                    i += 4;
-                }
-            }
-            VTFImageFormat::BGRA8888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(4) {
-                    rgba[i] = chunk[2];
-                    rgba[i + 1] = chunk[1];
-                    rgba[i + 2] = chunk[0];
-                    rgba[i + 3] = chunk[3];
-                    i += 4;
-                }
-            }
-            VTFImageFormat::DXT1 => {
-                texpresso::Format::Bc1.decompress(buf, mipmap.width as usize, mipmap.height as usize, &mut rgba);
-            }
-            VTFImageFormat::DXT3 => {
-                texpresso::Format::Bc2.decompress(buf, mipmap.width as usize, mipmap.height as usize, &mut rgba);
-            }
-            VTFImageFormat::DXT5 => {
-                texpresso::Format::Bc3.decompress(buf, mipmap.width as usize, mipmap.height as usize, &mut rgba);
-            }
-            VTFImageFormat::BGRX8888 => {
-                let mut i = 0;
-                for chunk in buf.chunks_exact(4) {
-                    rgba[i] = chunk[2];
-                    rgba[i + 1] = chunk[1];
-                    rgba[i + 2] = chunk[0];
-                    rgba[i + 3] = 255;
-                    i += 4;
-                }
-            }
-            _variant => unreachable!(),
-        };
 
-        Ok(VTFData {
-            width: mipmap.width,
-            height: mipmap.height,
-            rgba,
-        })
+    #[wasm_bindgen(getter)]
+    pub fn header(&self) -> VTFHeader {
+        self.0.header
     }
 }
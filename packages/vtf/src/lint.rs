@@ -0,0 +1,137 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{encode::full_mipmap_count, flags, VTFAlphaUsage, VTFExtractError, VTFImageFormat, VTF};
+
+const LARGE_TEXTURE_PIXELS: usize = 256 * 256;
+const HUGE_TEXTURE_SIZE: u16 = 1024;
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct VTFLints(pub Vec<VTFLint>);
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFLint {
+    pub kind: VTFLintKind,
+    pub message: String,
+    pub saving: u64,
+    pub cost: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Tsify)]
+pub enum VTFLintKind {
+    UncompressedOpaque,
+    ConstantAlpha,
+    MissingMipMaps,
+    NoMipHuge,
+    NonPowerOfTwo,
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn lint(&self, world: bool) -> Result<VTFLints, VTFExtractError> {
+        let header = &self.header;
+        let format = header.high_res_image_format;
+        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
+
+        let image_bytes = |format: VTFImageFormat, mipmap_count: u8| header.image_bytes(format, mipmap_count).map(|bytes| bytes as u64);
+        let current = image_bytes(format, header.mipmap_count)?;
+
+        let largest = mipmaps.len().checked_sub(1).ok_or(VTFExtractError::UnexpectedMipMap {
+            mipmap_count: header.mipmap_count,
+            found: 0,
+        })?;
+
+        // The widest alpha usage across frames, and the alpha value if every pixel of every frame shares it.
+        let alpha = (0..header.frames as usize)
+            .map(|frame_index| self.extract(largest, frame_index).map(|data| data.statistics()))
+            .try_fold(None, |alpha: Option<(VTFAlphaUsage, Option<u8>)>, statistics| {
+                statistics.map(|statistics| {
+                    let channel = &statistics.channels[3];
+                    let constant = (channel.min == channel.max).then_some(channel.min);
+
+                    Some(match alpha {
+                        None => (statistics.alpha, constant),
+                        Some((usage, previous)) => (usage.max(statistics.alpha), previous.filter(|previous| constant == Some(*previous))),
+                    })
+                })
+            })?;
+        let (alpha, constant_alpha) = alpha.unzip();
+        let constant_alpha = constant_alpha.flatten();
+
+        let mut lints = Vec::new();
+
+        let uncompressed = matches!(
+            format,
+            VTFImageFormat::RGBA8888
+                | VTFImageFormat::ABGR8888
+                | VTFImageFormat::RGB888
+                | VTFImageFormat::BGR888
+                | VTFImageFormat::ARGB8888
+                | VTFImageFormat::BGRA8888
+                | VTFImageFormat::BGRX8888
+        );
+
+        if uncompressed
+            && alpha == Some(VTFAlphaUsage::Opaque)
+            && header.flags & flags::NORMAL == 0
+            && header.width as usize * header.height as usize >= LARGE_TEXTURE_PIXELS
+        {
+            lints.push(VTFLint {
+                kind: VTFLintKind::UncompressedOpaque,
+                message: format!("{:?} {}x{} never uses alpha, DXT1 would be smaller", format, header.width, header.height),
+                saving: current.saturating_sub(image_bytes(VTFImageFormat::DXT1, header.mipmap_count)?),
+                cost: 0,
+            });
+        }
+
+        if let (VTFImageFormat::DXT3 | VTFImageFormat::DXT5, Some(value)) = (format, constant_alpha) {
+            lints.push(match value {
+                255 => VTFLint {
+                    kind: VTFLintKind::ConstantAlpha,
+                    message: format!("{:?} alpha is always 255, DXT1 would halve the size", format),
+                    saving: current.saturating_sub(image_bytes(VTFImageFormat::DXT1, header.mipmap_count)?),
+                    cost: 0,
+                },
+                _ => VTFLint {
+                    kind: VTFLintKind::ConstantAlpha,
+                    message: format!("{:?} alpha is always {}, the alpha channel carries no detail", format, value),
+                    saving: 0,
+                    cost: 0,
+                },
+            });
+        }
+
+        let full_mipmap_count = full_mipmap_count(header.width, header.height);
+
+        if world && header.flags & flags::NOMIP == 0 && header.mipmap_count < full_mipmap_count {
+            lints.push(VTFLint {
+                kind: VTFLintKind::MissingMipMaps,
+                message: format!("{} of {} mipmaps present", header.mipmap_count, full_mipmap_count),
+                saving: 0,
+                cost: image_bytes(format, full_mipmap_count)?.saturating_sub(current),
+            });
+        }
+
+        if header.flags & flags::NOMIP != 0 && header.width.max(header.height) > HUGE_TEXTURE_SIZE {
+            lints.push(VTFLint {
+                kind: VTFLintKind::NoMipHuge,
+                message: format!("NOMIP set on {}x{} texture", header.width, header.height),
+                saving: current.saturating_sub(image_bytes(format, 1)?),
+                cost: 0,
+            });
+        }
+
+        if !header.width.is_power_of_two() || !header.height.is_power_of_two() {
+            lints.push(VTFLint {
+                kind: VTFLintKind::NonPowerOfTwo,
+                message: format!("{}x{} is not a power of two", header.width, header.height),
+                saving: 0,
+                cost: 0,
+            });
+        }
+
+        Ok(VTFLints(lints))
+    }
+}
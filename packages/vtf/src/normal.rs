@@ -0,0 +1,127 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{flags, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat, VTF};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTFNormalKernel {
+    Sobel,
+    Scharr,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTFWrap {
+    Clamp,
+    Repeat,
+}
+
+pub(crate) struct HeightMap {
+    pub width: usize,
+    pub height: usize,
+    pub heights: Vec<f32>,
+    pub wrap: VTFWrap,
+}
+
+impl HeightMap {
+    pub fn new(data: &VTFData, wrap: VTFWrap) -> HeightMap {
+        HeightMap {
+            width: data.width as usize,
+            height: data.height as usize,
+            heights: data
+                .rgba
+                .chunks_exact(4)
+                .map(|pixel| (pixel[0] as f32 + pixel[1] as f32 + pixel[2] as f32) / (3.0 * 255.0))
+                .collect(),
+            wrap,
+        }
+    }
+
+    pub fn sample(&self, x: isize, y: isize) -> f32 {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = match self.wrap {
+            VTFWrap::Clamp => (x.clamp(0, width - 1), y.clamp(0, height - 1)),
+            VTFWrap::Repeat => (x.rem_euclid(width), y.rem_euclid(height)),
+        };
+        self.heights[(y * width + x) as usize]
+    }
+
+    pub fn gradient(&self, x: isize, y: isize, kernel: VTFNormalKernel) -> (f32, f32) {
+        let weights = match kernel {
+            VTFNormalKernel::Sobel => [1.0, 2.0, 1.0],
+            VTFNormalKernel::Scharr => [3.0, 10.0, 3.0],
+        };
+        let total = 2.0 * weights.iter().sum::<f32>();
+
+        let (mut dx, mut dy) = (0.0, 0.0);
+        for (i, weight) in weights.iter().enumerate() {
+            let offset = i as isize - 1;
+            dx += weight * (self.sample(x + 1, y + offset) - self.sample(x - 1, y + offset));
+            dy += weight * (self.sample(x + offset, y + 1) - self.sample(x + offset, y - 1));
+        }
+
+        (dx / total, dy / total)
+    }
+}
+
+impl VTFData {
+    pub fn normal_map(&self, bumpmap_scale: f32, kernel: VTFNormalKernel, wrap: VTFWrap) -> VTFData {
+        let heights = HeightMap::new(self, wrap);
+        let mut rgba = vec![0; self.rgba.len()];
+
+        for y in 0..heights.height {
+            for x in 0..heights.width {
+                let (dx, dy) = heights.gradient(x as isize, y as isize, kernel);
+                let normal = [-dx * bumpmap_scale, -dy * bumpmap_scale, 1.0];
+                let length = normal.iter().map(|value| value * value).sum::<f32>().sqrt();
+
+                let [r, g, b] = normal.map(|value| ((value / length * 0.5 + 0.5) * 255.0).round() as u8);
+                rgba[(y * heights.width + x) * 4..][..4].copy_from_slice(&[r, g, b, 255]);
+            }
+        }
+
+        VTFData {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+}
+
+impl VTF {
+    pub fn from_height_map(height_map: &VTFData, kernel: VTFNormalKernel, wrap: VTFWrap, options: &VTFEncodeOptions) -> Result<Vec<u8>, VTFEncodeError> {
+        let normal_map = height_map.normal_map(options.bumpmap_scale, kernel, wrap);
+
+        VTF::encode(
+            vec![normal_map],
+            &VTFEncodeOptions {
+                flags: options.flags | flags::NORMAL,
+                ..*options
+            },
+        )
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFFromHeightMap")]
+pub fn vtf_from_height_map(
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+    bumpmap_scale: f32,
+    kernel: VTFNormalKernel,
+    wrap: VTFWrap,
+    format: VTFImageFormat,
+) -> Result<Vec<u8>, VTFEncodeError> {
+    let height_map = VTFData::new(width, height, rgba)?;
+
+    VTF::from_height_map(
+        &height_map,
+        kernel,
+        wrap,
+        &VTFEncodeOptions {
+            format,
+            bumpmap_scale,
+            ..Default::default()
+        },
+    )
+}
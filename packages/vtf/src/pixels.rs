@@ -0,0 +1,178 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{f16_to_f32, flags, VTFExtractError, VTFImageFormat, VTF};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTFPixelType {
+    RGBA8,
+    RGBA16,
+    RGBA32F,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTFColourSpace {
+    SRGB,
+    Linear,
+    Data,
+}
+
+#[derive(Debug, Clone)]
+pub enum VTFPixels {
+    RGBA8(Vec<u8>),
+    RGBA16(Vec<u16>),
+    RGBA32F(Vec<f32>),
+}
+
+#[derive(Debug, Clone)]
+pub struct VTFImage {
+    pub width: u16,
+    pub height: u16,
+    pub colour_space: VTFColourSpace,
+    pub pixels: VTFPixels,
+}
+
+pub fn srgb_to_linear(value: f32) -> f32 {
+    match value {
+        ..=0.04045 => value / 12.92,
+        _ => ((value + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+pub fn linear_to_srgb(value: f32) -> f32 {
+    match value {
+        ..=0.0031308 => value * 12.92,
+        _ => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+    }
+}
+
+impl VTFImageFormat {
+    pub fn decode_texel_f32(&self, texel: &[u8]) -> Result<[f32; 4], VTFImageFormat> {
+        let le16 = |i: usize| u16::from_le_bytes([texel[i], texel[i + 1]]);
+
+        match self {
+            VTFImageFormat::RGBA16161616F => Ok([0, 2, 4, 6].map(|i| f16_to_f32(le16(i)))),
+            VTFImageFormat::RGBA16161616 => Ok([0, 2, 4, 6].map(|i| le16(i) as f32 / 65535.0)),
+            _ => Ok(self.decode_texel(texel)?.map(|value| value as f32 / 255.0)),
+        }
+    }
+
+    pub fn is_hdr(&self) -> bool {
+        matches!(self, VTFImageFormat::RGBA16161616F | VTFImageFormat::RGBA16161616)
+    }
+}
+
+fn quantise<T>(rgba: &[f32], max: f32, from: impl Fn(f32) -> T) -> Vec<T> {
+    rgba.iter().map(|value| from((value.clamp(0.0, 1.0) * max).round())).collect()
+}
+
+impl VTF {
+    pub fn extract_f32(&self, mipmap_index: usize, frame_index: usize, face_index: usize) -> Result<Vec<f32>, VTFExtractError> {
+        let format = self.header.high_res_image_format;
+
+        match (format.compression(), format.texel_bytes()) {
+            (Some(_), _) => Ok(self
+                .extract_face(mipmap_index, frame_index, face_index)?
+                .rgba
+                .into_iter()
+                .map(|value| value as f32 / 255.0)
+                .collect()),
+            (None, Some(texel_bytes)) => {
+                let (_, buf) = self.face(mipmap_index, frame_index, face_index)?;
+                let mut rgba = Vec::with_capacity(buf.len() / texel_bytes * 4);
+                for texel in buf.chunks_exact(texel_bytes) {
+                    rgba.extend(format.decode_texel_f32(texel)?);
+                }
+                Ok(rgba)
+            }
+            (None, None) => Err(format)?,
+        }
+    }
+
+    pub fn extract_in(&self, mipmap_index: usize, frame_index: usize, face_index: usize, colour_space: VTFColourSpace) -> Result<Vec<f32>, VTFExtractError> {
+        let mut rgba = self.extract_f32(mipmap_index, frame_index, face_index)?;
+
+        let convert: Option<fn(f32) -> f32> = match (self.colour_space(), colour_space) {
+            (VTFColourSpace::SRGB, VTFColourSpace::Linear) => Some(srgb_to_linear),
+            (VTFColourSpace::Linear, VTFColourSpace::SRGB) => Some(linear_to_srgb),
+            _ => None,
+        };
+
+        if let Some(convert) = convert {
+            for pixel in rgba.chunks_exact_mut(4) {
+                for value in &mut pixel[..3] {
+                    *value = convert(value.max(0.0));
+                }
+            }
+        }
+
+        Ok(rgba)
+    }
+
+    pub fn extract_as(
+        &self,
+        mipmap_index: usize,
+        frame_index: usize,
+        face_index: usize,
+        pixel_type: VTFPixelType,
+        colour_space: VTFColourSpace,
+    ) -> Result<VTFImage, VTFExtractError> {
+        let (mipmap, _) = self.face(mipmap_index, frame_index, face_index)?;
+        let rgba = self.extract_in(mipmap_index, frame_index, face_index, colour_space)?;
+
+        let pixels = match pixel_type {
+            VTFPixelType::RGBA8 => VTFPixels::RGBA8(quantise(&rgba, 255.0, |value| value as u8)),
+            VTFPixelType::RGBA16 => VTFPixels::RGBA16(quantise(&rgba, 65535.0, |value| value as u16)),
+            VTFPixelType::RGBA32F => VTFPixels::RGBA32F(rgba),
+        };
+
+        Ok(VTFImage {
+            width: mipmap.width,
+            height: mipmap.height,
+            colour_space: match self.colour_space() {
+                VTFColourSpace::Data => VTFColourSpace::Data,
+                _ => colour_space,
+            },
+            pixels,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn colour_space(&self) -> VTFColourSpace {
+        match self.header.flags {
+            flags if flags & (flags::NORMAL | flags::SSBUMP) != 0 => VTFColourSpace::Data,
+            _ if self.header.high_res_image_format.is_hdr() => VTFColourSpace::Linear,
+            flags if flags & flags::SRGB != 0 => VTFColourSpace::SRGB,
+            _ => VTFColourSpace::Linear,
+        }
+    }
+
+    pub fn extract_rgba8(&self, mipmap_index: usize, frame_index: usize, face_index: usize, colour_space: VTFColourSpace) -> Result<Vec<u8>, VTFExtractError> {
+        let rgba = self.extract_in(mipmap_index, frame_index, face_index, colour_space)?;
+        Ok(quantise(&rgba, 255.0, |value| value as u8))
+    }
+
+    pub fn extract_rgba16(
+        &self,
+        mipmap_index: usize,
+        frame_index: usize,
+        face_index: usize,
+        colour_space: VTFColourSpace,
+    ) -> Result<Vec<u16>, VTFExtractError> {
+        let rgba = self.extract_in(mipmap_index, frame_index, face_index, colour_space)?;
+        Ok(quantise(&rgba, 65535.0, |value| value as u16))
+    }
+
+    pub fn extract_rgba32f(
+        &self,
+        mipmap_index: usize,
+        frame_index: usize,
+        face_index: usize,
+        colour_space: VTFColourSpace,
+    ) -> Result<Vec<f32>, VTFExtractError> {
+        self.extract_in(mipmap_index, frame_index, face_index, colour_space)
+    }
+}
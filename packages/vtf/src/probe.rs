@@ -0,0 +1,18 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{VTFError, VTFHeader};
+
+pub const PROBE_BYTES: usize = 80;
+
+impl VTFHeader {
+    pub fn probe(buf: &[u8]) -> Result<VTFHeader, VTFError> {
+        let config = bincode::config::standard().with_fixed_int_encoding();
+        let (header, _) = bincode::decode_from_slice(&buf[..buf.len().min(PROBE_BYTES)], config)?;
+        Ok(header)
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFProbe")]
+pub fn vtf_probe(prefix: &[u8]) -> Result<VTFHeader, VTFError> {
+    VTFHeader::probe(prefix)
+}
@@ -0,0 +1,117 @@
+use crate::{VTFData, VTFExtractError, VTF};
+
+const GAP: usize = 4;
+const LABEL_HEIGHT: usize = GLYPH_HEIGHT + 4;
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+const fn glyph(char: char) -> [u8; GLYPH_HEIGHT] {
+    match char {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'x' => [0b000, 0b101, 0b010, 0b101, 0b000],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+struct Sheet {
+    width: usize,
+    rgba: Vec<u8>,
+}
+
+impl Sheet {
+    fn blit(&mut self, data: &VTFData, left: usize, top: usize) {
+        let row_bytes = data.width as usize * 4;
+        for (y, row) in data.rgba.chunks_exact(row_bytes).enumerate() {
+            let start = ((top + y) * self.width + left) * 4;
+            self.rgba[start..start + row_bytes].copy_from_slice(row);
+        }
+    }
+
+    fn label(&mut self, text: &str, left: usize, top: usize, width: usize) {
+        for y in top..top + LABEL_HEIGHT {
+            for x in left..left + width {
+                self.rgba[(y * self.width + x) * 4..][..4].copy_from_slice(&[0, 0, 0, 255]);
+            }
+        }
+
+        for (i, char) in text.chars().enumerate() {
+            let glyph_left = left + 2 + i * (GLYPH_WIDTH + 1);
+            for (y, row) in glyph(char).iter().enumerate() {
+                for x in 0..GLYPH_WIDTH {
+                    let (px, py) = (glyph_left + x, top + 2 + y);
+                    if row >> (GLYPH_WIDTH - 1 - x) & 1 == 1 && px < left + width {
+                        self.rgba[(py * self.width + px) * 4..][..4].copy_from_slice(&[255, 255, 255, 255]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl VTF {
+    pub fn contact_sheet(&self, all_frames: bool) -> Result<VTFData, VTFExtractError> {
+        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
+        let frames = if all_frames { self.header.frames as usize } else { 1 };
+
+        if frames == 0 {
+            return Err(VTFExtractError::UnexpectedFrame { frame_count: 0, found: 0 });
+        }
+
+        if mipmaps.is_empty() {
+            return Err(VTFExtractError::UnexpectedMipMap {
+                mipmap_count: self.header.mipmap_count,
+                found: 0,
+            });
+        }
+
+        let label = |width: u16, height: u16| format!("{}x{}", width, height);
+        let column_width = |width: u16, height: u16| (width as usize).max(2 + label(width, height).len() * (GLYPH_WIDTH + 1));
+
+        let row_height = mipmaps.iter().map(|mipmap| mipmap.height as usize).max().unwrap_or(0) + LABEL_HEIGHT;
+        let width = mipmaps
+            .iter()
+            .map(|mipmap| column_width(mipmap.width, mipmap.height) + GAP)
+            .sum::<usize>()
+            .saturating_sub(GAP);
+        let height = (row_height + GAP) * frames - GAP;
+
+        let (Ok(sheet_width), Ok(sheet_height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(VTFExtractError::UnexpectedSize { width, height });
+        };
+
+        let mut sheet = Sheet {
+            width,
+            rgba: vec![0; width * height * 4],
+        };
+
+        for frame_index in 0..frames {
+            let top = frame_index * (row_height + GAP);
+            let mut left = 0;
+
+            for mipmap_index in (0..mipmaps.len()).rev() {
+                let data = self.extract(mipmap_index, frame_index)?;
+                let column_width = column_width(data.width, data.height);
+
+                sheet.blit(&data, left, top);
+                sheet.label(&label(data.width, data.height), left, top + row_height - LABEL_HEIGHT, column_width);
+
+                left += column_width + GAP;
+            }
+        }
+
+        Ok(VTFData {
+            width: sheet_width,
+            height: sheet_height,
+            rgba: sheet.rgba,
+        })
+    }
+}
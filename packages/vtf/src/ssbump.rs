@@ -0,0 +1,148 @@
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{flags, normal::HeightMap, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat, VTFNormalKernel, VTFWrap, VTF};
+
+const AZIMUTHS: usize = 16;
+const ELEVATIONS: [f32; 4] = [0.25, 0.6, 1.2, 3.0];
+
+const OO_SQRT_3: f32 = 0.577_350_26;
+const OO_SQRT_6: f32 = 0.408_248_3;
+const SQRT_2_OVER_3: f32 = 0.816_496_6;
+
+pub const BUMP_BASIS: [[f32; 3]; 3] = [
+    [SQRT_2_OVER_3, 0.0, OO_SQRT_3],
+    [-OO_SQRT_6, FRAC_1_SQRT_2, OO_SQRT_3],
+    [-OO_SQRT_6, -FRAC_1_SQRT_2, OO_SQRT_3],
+];
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn normalise(value: [f32; 3]) -> [f32; 3] {
+    let length = dot(value, value).sqrt().max(f32::EPSILON);
+    value.map(|value| value / length)
+}
+
+impl HeightMap {
+    fn visible(&self, x: usize, y: usize, ray: [f32; 3], scale: f32, max_height: f32) -> bool {
+        let origin = self.sample(x as isize, y as isize) * scale;
+        let horizontal = (ray[0] * ray[0] + ray[1] * ray[1]).sqrt().max(f32::EPSILON);
+        let rise = ray[2] / horizontal;
+
+        let mut distance = 1.0;
+        while origin + distance * rise < max_height {
+            let sx = (x as f32 + ray[0] / horizontal * distance).round() as isize;
+            let sy = (y as f32 + ray[1] / horizontal * distance).round() as isize;
+            if self.sample(sx, sy) * scale > origin + distance * rise {
+                return false;
+            }
+            distance += 1.0;
+        }
+
+        true
+    }
+}
+
+impl VTFData {
+    pub fn ssbump(&self, bumpmap_scale: f32, wrap: VTFWrap) -> VTFData {
+        let heights = HeightMap::new(self, wrap);
+        let max_height = heights.heights.iter().copied().fold(0.0, f32::max) * bumpmap_scale;
+
+        let rays = (0..AZIMUTHS)
+            .flat_map(|azimuth| {
+                let angle = azimuth as f32 * 2.0 * PI / AZIMUTHS as f32;
+                ELEVATIONS.map(|z| normalise([angle.cos(), angle.sin(), z]))
+            })
+            .collect::<Vec<[f32; 3]>>();
+
+        let lobes = BUMP_BASIS.map(|basis| rays.iter().map(|ray| dot(*ray, basis).max(0.0)).sum::<f32>());
+
+        let mut rgba = vec![0; self.rgba.len()];
+
+        for y in 0..heights.height {
+            for x in 0..heights.width {
+                let (dx, dy) = heights.gradient(x as isize, y as isize, VTFNormalKernel::Sobel);
+                let normal = normalise([-dx * bumpmap_scale, -dy * bumpmap_scale, 1.0]);
+
+                let mut visibility = [0.0; 3];
+                for ray in rays.iter().filter(|ray| heights.visible(x, y, **ray, bumpmap_scale, max_height)) {
+                    for (visibility, basis) in visibility.iter_mut().zip(BUMP_BASIS) {
+                        *visibility += dot(*ray, basis).max(0.0);
+                    }
+                }
+
+                let mut pixel = [0, 0, 0, 255];
+                for (i, basis) in BUMP_BASIS.iter().enumerate() {
+                    let value = visibility[i] / lobes[i] * dot(normal, *basis).max(0.0);
+                    pixel[i] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+
+                rgba[(y * heights.width + x) * 4..][..4].copy_from_slice(&pixel);
+            }
+        }
+
+        VTFData {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+
+    pub fn ssbump_to_normal(&self) -> VTFData {
+        let rgba = self
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| {
+                let normal = normalise((0..3).fold([0.0; 3], |normal, i| {
+                    let weight = pixel[i] as f32 / 255.0;
+                    [0, 1, 2].map(|c| normal[c] + BUMP_BASIS[i][c] * weight)
+                }));
+                let [r, g, b] = normal.map(|value| ((value * 0.5 + 0.5) * 255.0).round() as u8);
+                [r, g, b, 255]
+            })
+            .collect();
+
+        VTFData {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+}
+
+impl VTF {
+    pub fn ssbump_from_height_map(height_map: &VTFData, wrap: VTFWrap, options: &VTFEncodeOptions) -> Result<Vec<u8>, VTFEncodeError> {
+        VTF::encode(
+            vec![height_map.ssbump(options.bumpmap_scale, wrap)],
+            &VTFEncodeOptions {
+                flags: options.flags | flags::SSBUMP,
+                ..*options
+            },
+        )
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFSSBumpFromHeightMap")]
+pub fn vtf_ssbump_from_height_map(
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+    bumpmap_scale: f32,
+    wrap: VTFWrap,
+    format: VTFImageFormat,
+) -> Result<Vec<u8>, VTFEncodeError> {
+    let height_map = VTFData::new(width, height, rgba)?;
+
+    VTF::ssbump_from_height_map(
+        &height_map,
+        wrap,
+        &VTFEncodeOptions {
+            format,
+            bumpmap_scale,
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,90 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{VTFData, VTFExtractError, VTF};
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct VTFStatistics {
+    pub width: u16,
+    pub height: u16,
+    pub channels: [VTFChannelStatistics; 4],
+    pub average: [u8; 4],
+    pub alpha: VTFAlphaUsage,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+pub struct VTFChannelStatistics {
+    pub min: u8,
+    pub max: u8,
+    pub mean: f64,
+    pub histogram: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Tsify)]
+pub enum VTFAlphaUsage {
+    Opaque,
+    OneBit,
+    EightBit,
+}
+
+impl VTFData {
+    pub fn statistics(&self) -> VTFStatistics {
+        let mut histograms = [[0u32; 256]; 4];
+        let mut weighted = [0u64; 3];
+        let mut alpha_sum = 0u64;
+
+        for pixel in self.rgba.chunks_exact(4) {
+            for (histogram, value) in histograms.iter_mut().zip(pixel) {
+                histogram[*value as usize] += 1;
+            }
+
+            for (sum, value) in weighted.iter_mut().zip(pixel) {
+                *sum += *value as u64 * pixel[3] as u64;
+            }
+            alpha_sum += pixel[3] as u64;
+        }
+
+        let pixels = (self.rgba.len() / 4) as u64;
+
+        let channels = histograms.map(|histogram| {
+            let min = histogram.iter().position(|count| *count != 0).unwrap_or(0) as u8;
+            let max = histogram.iter().rposition(|count| *count != 0).unwrap_or(0) as u8;
+            let sum = histogram.iter().enumerate().map(|(value, count)| value as u64 * *count as u64).sum::<u64>();
+
+            VTFChannelStatistics {
+                min,
+                max,
+                mean: if pixels == 0 { 0.0 } else { sum as f64 / pixels as f64 },
+                histogram: histogram.to_vec(),
+            }
+        });
+
+        let average = {
+            let [r, g, b] = weighted.map(|sum| sum.checked_div(alpha_sum).unwrap_or(0) as u8);
+            [r, g, b, alpha_sum.checked_div(pixels).unwrap_or(0) as u8]
+        };
+
+        let alpha = match histograms[3][1..255].iter().any(|count| *count != 0) {
+            true => VTFAlphaUsage::EightBit,
+            false if histograms[3][0] != 0 => VTFAlphaUsage::OneBit,
+            false => VTFAlphaUsage::Opaque,
+        };
+
+        VTFStatistics {
+            width: self.width,
+            height: self.height,
+            channels,
+            average,
+            alpha,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn statistics(&self, mipmap_index: usize, frame_index: usize) -> Result<VTFStatistics, VTFExtractError> {
+        Ok(self.extract(mipmap_index, frame_index)?.statistics())
+    }
+}
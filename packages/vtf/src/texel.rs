@@ -0,0 +1,113 @@
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{expand, f16_to_f32, VTFExtractError, VTFImageFormat, VTF};
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct VTFTexel {
+    pub x: u16,
+    pub y: u16,
+    pub rgba: [u8; 4],
+    pub raw: VTFRawTexel,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[serde(tag = "type", content = "value")]
+pub enum VTFRawTexel {
+    Bytes(Vec<u8>),
+    Block {
+        block: Vec<u8>,
+        colours: [[u8; 3]; 2],
+        colour_index: u8,
+        alpha: Option<VTFBlockAlpha>,
+    },
+    Float([f32; 4]),
+    Short([u16; 4]),
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[serde(tag = "type", content = "value")]
+pub enum VTFBlockAlpha {
+    Explicit(u8),
+    Interpolated { alphas: [u8; 2], index: u8 },
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn texel(&self, mipmap_index: usize, frame_index: usize, x: u16, y: u16) -> Result<VTFTexel, VTFExtractError> {
+        let (mipmap, buf) = self.frame(mipmap_index, frame_index)?;
+        let format = self.header.high_res_image_format;
+
+        if x >= mipmap.width || y >= mipmap.height {
+            return Err(VTFExtractError::UnexpectedTexel {
+                width: mipmap.width,
+                height: mipmap.height,
+                x,
+                y,
+            });
+        }
+
+        let (rgba, raw) = match (format.compression(), format.texel_bytes()) {
+            (Some(compression), _) => {
+                let block_size = compression.block_size();
+                let blocks_wide = texpresso::num_blocks(mipmap.width as usize);
+                let offset = ((y as usize / 4) * blocks_wide + (x as usize / 4)) * block_size;
+                let block = buf
+                    .get(offset..offset + block_size)
+                    .ok_or(VTFExtractError::UnexpectedEnd { additional: block_size })?;
+
+                let i = (y as usize % 4) * 4 + (x as usize % 4);
+                let colour_block = &block[block_size - 8..];
+                let colour = |i: usize| {
+                    let value = u16::from_le_bytes([colour_block[i], colour_block[i + 1]]);
+                    [expand::<5>(value >> 11), expand::<6>((value >> 5) & 0x3F), expand::<5>(value & 0x1F)]
+                };
+                let colours = [colour(0), colour(2)];
+
+                let colour_index = (u32::from_le_bytes([colour_block[4], colour_block[5], colour_block[6], colour_block[7]]) >> (2 * i)) as u8 & 0x3;
+
+                let alpha = match format {
+                    VTFImageFormat::DXT3 => Some(VTFBlockAlpha::Explicit((block[i / 2] >> (4 * (i % 2))) & 0xF)),
+                    VTFImageFormat::DXT5 => {
+                        let indices = u64::from_le_bytes([block[2], block[3], block[4], block[5], block[6], block[7], 0, 0]);
+                        Some(VTFBlockAlpha::Interpolated {
+                            alphas: [block[0], block[1]],
+                            index: (indices >> (3 * i)) as u8 & 0x7,
+                        })
+                    }
+                    _ => None,
+                };
+
+                (
+                    compression.decompress_block(block)[i],
+                    VTFRawTexel::Block {
+                        block: block.to_vec(),
+                        colours,
+                        colour_index,
+                        alpha,
+                    },
+                )
+            }
+            (None, Some(texel_bytes)) => {
+                let offset = (y as usize * mipmap.width as usize + x as usize) * texel_bytes;
+                let texel = buf
+                    .get(offset..offset + texel_bytes)
+                    .ok_or(VTFExtractError::UnexpectedEnd { additional: texel_bytes })?;
+                let le16 = |i: usize| u16::from_le_bytes([texel[i], texel[i + 1]]);
+
+                let raw = match format {
+                    VTFImageFormat::RGBA16161616F => VTFRawTexel::Float([0, 2, 4, 6].map(|i| f16_to_f32(le16(i)))),
+                    VTFImageFormat::RGBA16161616 => VTFRawTexel::Short([0, 2, 4, 6].map(le16)),
+                    _ => VTFRawTexel::Bytes(texel.to_vec()),
+                };
+
+                (format.decode_texel(texel)?, raw)
+            }
+            (None, None) => Err(format)?,
+        };
+
+        Ok(VTFTexel { x, y, rgba, raw })
+    }
+}
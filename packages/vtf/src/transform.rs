@@ -0,0 +1,248 @@
+use serde::Deserialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat, VTF};
+
+#[derive(Debug, Deserialize, Tsify)]
+#[tsify(from_wasm_abi)]
+pub struct VTFTransforms(pub Vec<VTFTransform>);
+
+#[derive(Debug, Clone, Copy, Deserialize, Tsify)]
+#[serde(tag = "type", content = "value")]
+pub enum VTFTransform {
+    FlipHorizontal,
+    FlipVertical,
+    Rotate(u8),
+    Crop { x: u16, y: u16, width: u16, height: u16 },
+    ResizePowerOfTwo,
+    Swizzle([u8; 4]),
+    Invert(u8),
+    Premultiply,
+    Unpremultiply,
+}
+
+impl VTFData {
+    fn remap(&self, width: u16, height: u16, source: impl Fn(usize, usize) -> (usize, usize)) -> VTFData {
+        let mut rgba = vec![0; width as usize * height as usize * 4];
+
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let (sx, sy) = source(x, y);
+                let i = (sy * self.width as usize + sx) * 4;
+                rgba[(y * width as usize + x) * 4..][..4].copy_from_slice(&self.rgba[i..i + 4]);
+            }
+        }
+
+        VTFData { width, height, rgba }
+    }
+
+    fn map_pixels(&self, f: impl Fn([u8; 4]) -> [u8; 4]) -> VTFData {
+        VTFData {
+            width: self.width,
+            height: self.height,
+            rgba: self
+                .rgba
+                .chunks_exact(4)
+                .flat_map(|pixel| f([pixel[0], pixel[1], pixel[2], pixel[3]]))
+                .collect(),
+        }
+    }
+
+    pub fn flip_horizontal(&self) -> VTFData {
+        let width = self.width as usize;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    pub fn flip_vertical(&self) -> VTFData {
+        let height = self.height as usize;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+
+    pub fn rotate(&self, quarter_turns: u8) -> VTFData {
+        let (width, height) = (self.width as usize, self.height as usize);
+        match quarter_turns % 4 {
+            1 => self.remap(self.height, self.width, |x, y| (y, height - 1 - x)),
+            2 => self.remap(self.width, self.height, |x, y| (width - 1 - x, height - 1 - y)),
+            3 => self.remap(self.height, self.width, |x, y| (width - 1 - y, x)),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn crop(&self, x: u16, y: u16, width: u16, height: u16) -> Result<VTFData, VTFEncodeError> {
+        if width == 0 || height == 0 || x as usize + width as usize > self.width as usize || y as usize + height as usize > self.height as usize {
+            return Err(VTFEncodeError::UnexpectedSize {
+                width: self.width,
+                height: self.height,
+                found_width: x.saturating_add(width),
+                found_height: y.saturating_add(height),
+            });
+        }
+
+        Ok(self.remap(width, height, |sx, sy| (x as usize + sx, y as usize + sy)))
+    }
+
+    pub fn resize(&self, width: u16, height: u16) -> Result<VTFData, VTFEncodeError> {
+        if width == 0 || height == 0 || self.width == 0 || self.height == 0 {
+            return Err(VTFEncodeError::UnexpectedSize {
+                width: self.width,
+                height: self.height,
+                found_width: width,
+                found_height: height,
+            });
+        }
+
+        let (source_width, source_height) = (self.width as usize, self.height as usize);
+        let mut rgba = vec![0; width as usize * height as usize * 4];
+
+        for y in 0..height as usize {
+            let fy = ((y as f32 + 0.5) * source_height as f32 / height as f32 - 0.5).clamp(0.0, (source_height - 1) as f32);
+            let (y0, ty) = (fy as usize, fy.fract());
+            let y1 = (y0 + 1).min(source_height - 1);
+
+            for x in 0..width as usize {
+                let fx = ((x as f32 + 0.5) * source_width as f32 / width as f32 - 0.5).clamp(0.0, (source_width - 1) as f32);
+                let (x0, tx) = (fx as usize, fx.fract());
+                let x1 = (x0 + 1).min(source_width - 1);
+
+                for c in 0..4 {
+                    let sample = |x: usize, y: usize| self.rgba[(y * source_width + x) * 4 + c] as f32;
+                    let top = sample(x0, y0) * (1.0 - tx) + sample(x1, y0) * tx;
+                    let bottom = sample(x0, y1) * (1.0 - tx) + sample(x1, y1) * tx;
+                    rgba[(y * width as usize + x) * 4 + c] = (top * (1.0 - ty) + bottom * ty).round() as u8;
+                }
+            }
+        }
+
+        Ok(VTFData { width, height, rgba })
+    }
+
+    pub fn resize_power_of_two(&self) -> Result<VTFData, VTFEncodeError> {
+        let (width, height) = (self.width.next_power_of_two(), self.height.next_power_of_two());
+        match (width, height) == (self.width, self.height) {
+            true => Ok(self.clone()),
+            false => self.resize(width, height),
+        }
+    }
+
+    pub fn swizzle(&self, channels: [u8; 4]) -> Result<VTFData, VTFEncodeError> {
+        if let Some(found) = channels.into_iter().find(|c| *c > 3) {
+            return Err(VTFEncodeError::UnexpectedChannel { found });
+        }
+
+        Ok(self.map_pixels(|pixel| channels.map(|c| pixel[c as usize])))
+    }
+
+    pub fn invert(&self, channel: u8) -> Result<VTFData, VTFEncodeError> {
+        if channel > 3 {
+            return Err(VTFEncodeError::UnexpectedChannel { found: channel });
+        }
+
+        Ok(self.map_pixels(|mut pixel| {
+            pixel[channel as usize] = 255 - pixel[channel as usize];
+            pixel
+        }))
+    }
+
+    pub fn premultiply(&self) -> VTFData {
+        self.map_pixels(|[r, g, b, a]| {
+            let [r, g, b] = [r, g, b].map(|value| ((value as u32 * a as u32 + 127) / 255) as u8);
+            [r, g, b, a]
+        })
+    }
+
+    pub fn unpremultiply(&self) -> VTFData {
+        self.map_pixels(|[r, g, b, a]| match a {
+            0 => [r, g, b, a],
+            _ => {
+                let [r, g, b] = [r, g, b].map(|value| ((value as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8);
+                [r, g, b, a]
+            }
+        })
+    }
+
+    pub fn transform(&self, transform: VTFTransform) -> Result<VTFData, VTFEncodeError> {
+        match transform {
+            VTFTransform::FlipHorizontal => Ok(self.flip_horizontal()),
+            VTFTransform::FlipVertical => Ok(self.flip_vertical()),
+            VTFTransform::Rotate(quarter_turns) => Ok(self.rotate(quarter_turns)),
+            VTFTransform::Crop { x, y, width, height } => self.crop(x, y, width, height),
+            VTFTransform::ResizePowerOfTwo => self.resize_power_of_two(),
+            VTFTransform::Swizzle(channels) => self.swizzle(channels),
+            VTFTransform::Invert(channel) => self.invert(channel),
+            VTFTransform::Premultiply => Ok(self.premultiply()),
+            VTFTransform::Unpremultiply => Ok(self.unpremultiply()),
+        }
+    }
+
+    pub fn pack(channels: [Option<&VTFData>; 4]) -> Result<VTFData, VTFEncodeError> {
+        let Some(first) = channels.iter().flatten().next() else {
+            return Err(VTFEncodeError::UnexpectedImageCount { faces: 1, found: 0 });
+        };
+        let (width, height) = (first.width, first.height);
+
+        if let Some(image) = channels.iter().flatten().find(|image| image.width != width || image.height != height) {
+            return Err(VTFEncodeError::UnexpectedSize {
+                width,
+                height,
+                found_width: image.width,
+                found_height: image.height,
+            });
+        }
+
+        let mut rgba = [0, 0, 0, 255].repeat(width as usize * height as usize);
+        for (c, image) in channels.iter().enumerate() {
+            if let Some(image) = image {
+                for (pixel, source) in rgba.chunks_exact_mut(4).zip(image.rgba.chunks_exact(4)) {
+                    pixel[c] = ((source[0] as u16 + source[1] as u16 + source[2] as u16) / 3) as u8;
+                }
+            }
+        }
+
+        Ok(VTFData { width, height, rgba })
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    pub fn transform(&self, transforms: VTFTransforms) -> Result<Vec<u8>, VTFEncodeError> {
+        if self.header.high_res_image_format.is_hdr() {
+            return Err(VTFEncodeError::FormatError(self.header.high_res_image_format));
+        }
+
+        let largest = (self.header.mipmap_count as usize).saturating_sub(1);
+        let faces = self.header.faces();
+
+        let images = (0..self.header.frames as usize)
+            .flat_map(|frame_index| (0..faces).map(move |face_index| (frame_index, face_index)))
+            .map(|(frame_index, face_index)| {
+                transforms
+                    .0
+                    .iter()
+                    .try_fold(self.extract_face(largest, frame_index, face_index)?, |image, transform| {
+                        image.transform(*transform)
+                    })
+            })
+            .collect::<Result<Vec<VTFData>, VTFEncodeError>>()?;
+
+        VTF::encode_with_extra(images, &self.encode_options(), &self.extra_resources()?)
+    }
+}
+
+#[wasm_bindgen(js_name = "VTFPackChannels")]
+pub fn vtf_pack_channels(
+    width: u16,
+    height: u16,
+    r: Option<Vec<u8>>,
+    g: Option<Vec<u8>>,
+    b: Option<Vec<u8>>,
+    a: Option<Vec<u8>>,
+    format: VTFImageFormat,
+) -> Result<Vec<u8>, VTFEncodeError> {
+    let [r, g, b, a] = [r, g, b, a].map(|rgba| rgba.map(|rgba| VTFData::new(width, height, rgba)).transpose());
+    let (r, g, b, a) = (r?, g?, b?, a?);
+
+    let packed = VTFData::pack([r.as_ref(), g.as_ref(), b.as_ref(), a.as_ref()])?;
+
+    VTF::encode(vec![packed], &VTFEncodeOptions { format, ..Default::default() })
+}
@@ -0,0 +1,252 @@
+use std::{
+    borrow::Cow,
+    io::{BufReader, Cursor, Seek, SeekFrom},
+};
+
+use bincode::error::DecodeError;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{encode, VTFData, VTFError, VTFExtractError, VTFFrame, VTFHeader, VTFImageFormat, VTFMipMap, VTFResourceEntryInfo, VTF};
+
+#[derive(Debug)]
+pub struct VTFRef<'a> {
+    pub buf: Cow<'a, [u8]>,
+    pub header: VTFHeader,
+    pub resources: Option<Vec<VTFResourceEntryInfo>>,
+    pub mipmaps: Result<Vec<VTFMipMap>, VTFExtractError>,
+}
+
+fn decode_into(format: VTFImageFormat, mipmap: &VTFMipMap, buf: &[u8], out: &mut [u8]) -> Result<(), VTFExtractError> {
+    let expected = mipmap.width as usize * mipmap.height as usize * 4;
+    if out.len() != expected {
+        return Err(VTFExtractError::UnexpectedBufferLength { expected, found: out.len() });
+    }
+
+    match (format.compression(), format.texel_bytes()) {
+        (Some(compression), _) => {
+            compression.decompress(buf, mipmap.width as usize, mipmap.height as usize, out);
+        }
+        (None, Some(texel_bytes)) => {
+            for (texel, pixel) in buf.chunks_exact(texel_bytes).zip(out.chunks_exact_mut(4)) {
+                pixel.copy_from_slice(&format.decode_texel(texel)?);
+            }
+        }
+        (None, None) => Err(format)?,
+    };
+
+    Ok(())
+}
+
+type Parsed = (VTFHeader, Option<Vec<VTFResourceEntryInfo>>, Result<Vec<VTFMipMap>, VTFExtractError>);
+
+fn parse(buf: &[u8]) -> Result<Parsed, VTFError> {
+    let mut reader = BufReader::new(Cursor::new(buf));
+    let config = bincode::config::standard().with_fixed_int_encoding();
+
+    let header: VTFHeader = bincode::decode_from_reader(&mut reader, config)?;
+
+    if header.version_major >= 7 && header.version_minor >= 2 {
+        let _depth: u16 = bincode::decode_from_reader(&mut reader, config)?;
+    }
+
+    let num_resources_option = if header.version_major >= 7 && header.version_minor >= 3 {
+        let _padding2: [u8; 3] = bincode::decode_from_reader(&mut reader, config)?;
+        let num_resources: u32 = bincode::decode_from_reader(&mut reader, config)?;
+        Some(num_resources)
+    } else {
+        None
+    };
+
+    let _padding3: [u8; 8] = bincode::decode_from_reader(&mut reader, config)?;
+
+    let resources = match num_resources_option {
+        Some(num_resources) => Some(
+            (0..num_resources)
+                .map(|_| bincode::decode_from_reader::<VTFResourceEntryInfo, _, _>(&mut reader, config))
+                .collect::<Result<Vec<VTFResourceEntryInfo>, DecodeError>>()?,
+        ),
+        None => None,
+    };
+
+    reader
+        .seek(SeekFrom::Start(header.header_size as u64))
+        .map_err(|_| DecodeError::UnexpectedEnd {
+            additional: header.header_size as usize,
+        })?;
+
+    let thumbnail_bytes = header.thumbnail_bytes();
+    reader
+        .seek_relative(thumbnail_bytes as i64)
+        .map_err(|_| DecodeError::UnexpectedEnd { additional: thumbnail_bytes })?;
+
+    if let Some(image) = resources.iter().flatten().find(|resource| resource._tag == encode::IMAGE_TAG) {
+        reader.seek(SeekFrom::Start(image.offset as u64)).map_err(|_| DecodeError::UnexpectedEnd {
+            additional: image.offset as usize,
+        })?;
+    }
+
+    let mipmaps = (0..header.mipmap_count)
+        .rev()
+        .map(|i| -> Result<VTFMipMap, VTFExtractError> {
+            let width = (header.width as usize >> i).max(1);
+            let height = (header.height as usize >> i).max(1);
+
+            let bytes = header.high_res_image_format.bytes(width, height)?;
+            // Each frame holds all of its faces back to back, `frame.bytes` is the size of one face.
+            let faces = header.faces();
+
+            let frames = (0..header.frames)
+                .map(|_| {
+                    let offset = reader.stream_position().unwrap() as usize;
+                    reader
+                        .seek_relative((bytes * faces) as i64)
+                        .map_err(|_| VTFExtractError::UnexpectedEnd { additional: bytes * faces })?;
+                    Ok(VTFFrame { offset, bytes })
+                })
+                .collect::<Result<Vec<VTFFrame>, VTFExtractError>>()?;
+
+            Ok(VTFMipMap {
+                width: width as u16,
+                height: height as u16,
+                faces,
+                frames,
+            })
+        })
+        .collect::<Result<Vec<VTFMipMap>, VTFExtractError>>();
+
+    Ok((header, resources, mipmaps))
+}
+
+impl<'a> VTFRef<'a> {
+    pub fn new(buf: impl Into<Cow<'a, [u8]>>) -> Result<VTFRef<'a>, VTFError> {
+        let buf = buf.into();
+        let (header, resources, mipmaps) = parse(&buf)?;
+
+        Ok(VTFRef {
+            buf,
+            header,
+            resources,
+            mipmaps,
+        })
+    }
+
+    pub fn frame(&self, mipmap_index: usize, frame_index: usize) -> Result<(&VTFMipMap, &[u8]), VTFExtractError> {
+        self.face(mipmap_index, frame_index, 0)
+    }
+
+    pub fn face(&self, mipmap_index: usize, frame_index: usize, face_index: usize) -> Result<(&VTFMipMap, &[u8]), VTFExtractError> {
+        let mipmaps = self.mipmaps.as_ref().map_err(|err| err.clone())?;
+
+        let mipmap = mipmaps.get(mipmap_index).ok_or(VTFExtractError::UnexpectedMipMap {
+            mipmap_count: self.header.mipmap_count,
+            found: mipmap_index,
+        })?;
+
+        let frame = mipmap.frames.get(frame_index).ok_or(VTFExtractError::UnexpectedFrame {
+            frame_count: self.header.frames,
+            found: frame_index,
+        })?;
+
+        if face_index >= mipmap.faces {
+            return Err(VTFExtractError::UnexpectedFace {
+                face_count: mipmap.faces,
+                found: face_index,
+            });
+        }
+
+        let offset = frame.offset + face_index * frame.bytes;
+        let buf = self
+            .buf
+            .get(offset..(offset + frame.bytes))
+            .ok_or(VTFExtractError::UnexpectedEnd { additional: frame.bytes })?;
+
+        Ok((mipmap, buf))
+    }
+
+    pub fn extract(&self, mipmap_index: usize, frame_index: usize) -> Result<VTFData, VTFExtractError> {
+        self.extract_face(mipmap_index, frame_index, 0)
+    }
+
+    pub fn extract_face(&self, mipmap_index: usize, frame_index: usize, face_index: usize) -> Result<VTFData, VTFExtractError> {
+        let (mipmap, _) = self.face(mipmap_index, frame_index, face_index)?;
+
+        let mut rgba = vec![0; mipmap.width as usize * mipmap.height as usize * 4];
+        self.extract_face_into(mipmap_index, frame_index, face_index, &mut rgba)?;
+
+        Ok(VTFData {
+            width: mipmap.width,
+            height: mipmap.height,
+            rgba,
+        })
+    }
+
+    pub fn extract_into(&self, mipmap_index: usize, frame_index: usize, out: &mut [u8]) -> Result<(), VTFExtractError> {
+        self.extract_face_into(mipmap_index, frame_index, 0, out)
+    }
+
+    pub fn extract_face_into(&self, mipmap_index: usize, frame_index: usize, face_index: usize, out: &mut [u8]) -> Result<(), VTFExtractError> {
+        let (mipmap, buf) = self.face(mipmap_index, frame_index, face_index)?;
+        decode_into(self.header.high_res_image_format, mipmap, buf, out)
+    }
+}
+
+/// Fixed-capacity RGBA buffer in wasm memory that JS reads without copying.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct VTFPixelBuffer {
+    pub width: u16,
+    pub height: u16,
+    rgba: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl VTFPixelBuffer {
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: usize) -> VTFPixelBuffer {
+        VTFPixelBuffer {
+            width: 0,
+            height: 0,
+            rgba: vec![0; capacity],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.rgba.len()
+    }
+
+    /// Views the pixels written by the last `extract_to`. The view is invalidated by the next
+    /// `extract_to`, by freeing this buffer, and by wasm memory growing, which detaches it.
+    /// Take a fresh view after any of those instead of keeping one around.
+    pub fn view(&self) -> Uint8Array {
+        let len = self.width as usize * self.height as usize * 4;
+        // SAFETY: `rgba` is allocated once in `new` and never resized, so the view stays within
+        // live memory until the buffer is freed. The caller must follow the rules documented above.
+        unsafe { Uint8Array::view(&self.rgba[..len]) }
+    }
+}
+
+#[wasm_bindgen]
+impl VTF {
+    /// Allocates a pixel buffer large enough for any face of this texture.
+    pub fn pixel_buffer(&self) -> VTFPixelBuffer {
+        VTFPixelBuffer::new(self.header.width as usize * self.header.height as usize * 4)
+    }
+
+    pub fn extract_to(&self, mipmap_index: usize, frame_index: usize, face_index: usize, target: &mut VTFPixelBuffer) -> Result<(), VTFExtractError> {
+        let (mipmap, buf) = self.face(mipmap_index, frame_index, face_index)?;
+
+        let expected = mipmap.width as usize * mipmap.height as usize * 4;
+        let capacity = target.rgba.len();
+        let out = target
+            .rgba
+            .get_mut(..expected)
+            .ok_or(VTFExtractError::UnexpectedBufferLength { expected, found: capacity })?;
+        decode_into(self.header.high_res_image_format, mipmap, buf, out)?;
+
+        target.width = mipmap.width;
+        target.height = mipmap.height;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use vtf::{vtf_from_height_map, VTFData, VTFEncodeError, VTFEncodeOptions, VTFImageFormat, VTFNormalKernel, VTFWrap, VTF};
+
+fn is_unexpected_dimensions<T>(result: Result<T, VTFEncodeError>) -> bool {
+    matches!(result, Err(VTFEncodeError::UnexpectedDimensions { .. }))
+}
+
+#[test]
+fn data_rejects_zero_dimensions() {
+    assert!(is_unexpected_dimensions(VTFData::new(0, 4, vec![])));
+    assert!(is_unexpected_dimensions(VTFData::new(4, 0, vec![])));
+    assert!(is_unexpected_dimensions(VTFData::new(0, 0, vec![])));
+}
+
+#[test]
+fn height_map_rejects_zero_dimensions() {
+    let result = vtf_from_height_map(0, 4, vec![], 1.0, VTFNormalKernel::Sobel, VTFWrap::Clamp, VTFImageFormat::RGBA8888);
+    assert!(is_unexpected_dimensions(result));
+}
+
+#[test]
+fn encoder_rejects_zero_dimensions() {
+    let options = VTFEncodeOptions {
+        format: VTFImageFormat::RGBA8888,
+        ..Default::default()
+    };
+
+    let narrow = VTFData {
+        width: 0,
+        height: 4,
+        rgba: vec![],
+    };
+    assert!(is_unexpected_dimensions(VTF::encode(vec![narrow], &options)));
+
+    let empty = VTFData {
+        width: 0,
+        height: 0,
+        rgba: vec![],
+    };
+    let options = VTFEncodeOptions {
+        mipmap_count: Some(4),
+        ..options
+    };
+    assert!(is_unexpected_dimensions(VTF::encode(vec![empty], &options)));
+}
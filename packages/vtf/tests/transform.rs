@@ -0,0 +1,23 @@
+use vtf::{VTFData, VTFEncodeError, VTFTransform};
+
+fn pixel() -> VTFData {
+    VTFData::new(1, 1, vec![10, 20, 30, 40]).unwrap()
+}
+
+#[test]
+fn swizzle_and_invert_reorder_channels() {
+    assert_eq!(pixel().transform(VTFTransform::Swizzle([3, 2, 1, 0])).unwrap().rgba, [40, 30, 20, 10]);
+    assert_eq!(pixel().transform(VTFTransform::Invert(3)).unwrap().rgba, [10, 20, 30, 215]);
+}
+
+#[test]
+fn swizzle_and_invert_reject_unknown_channels() {
+    assert!(matches!(
+        pixel().transform(VTFTransform::Swizzle([0, 1, 2, 4])),
+        Err(VTFEncodeError::UnexpectedChannel { found: 4 })
+    ));
+    assert!(matches!(
+        pixel().transform(VTFTransform::Invert(7)),
+        Err(VTFEncodeError::UnexpectedChannel { found: 7 })
+    ));
+}